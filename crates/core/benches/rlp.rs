@@ -0,0 +1,49 @@
+use std::{fs::File, io::BufReader};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use ethrex_core::{
+    rlp::{decode::RLPDecode, encode::RLPEncode},
+    types::{Block, Transaction},
+};
+use serde_json::Value;
+
+fn read_fixture(kind: &str, index: usize) -> Vec<u8> {
+    let file = File::open(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../test_data/rlp_fixtures.json"
+    ))
+    .expect("Failed to open RLP fixtures file");
+    let fixtures: Value =
+        serde_json::from_reader(BufReader::new(file)).expect("Failed to read RLP fixtures");
+    let rlp = fixtures[kind][index]["rlp"]
+        .as_str()
+        .expect("Fixture should have an rlp field");
+    hex::decode(rlp.trim_start_matches("0x")).expect("Fixture rlp should be hex encoded")
+}
+
+fn block_benchmark(c: &mut Criterion) {
+    let rlp = read_fixture("blocks", 1);
+    let block = Block::decode(&rlp).unwrap();
+
+    c.bench_function("decode block", |b| {
+        b.iter(|| Block::decode(black_box(&rlp)).unwrap())
+    });
+    c.bench_function("encode block", |b| {
+        b.iter(|| {
+            let mut buf = Vec::new();
+            black_box(&block).encode(&mut buf);
+            buf
+        })
+    });
+}
+
+fn transaction_benchmark(c: &mut Criterion) {
+    let rlp = read_fixture("transactions", 0);
+
+    c.bench_function("decode legacy transaction", |b| {
+        b.iter(|| Transaction::decode(black_box(&rlp)).unwrap())
+    });
+}
+
+criterion_group!(rlp, block_benchmark, transaction_benchmark);
+criterion_main!(rlp);
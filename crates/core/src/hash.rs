@@ -0,0 +1,42 @@
+//! Keccak-256 hashing, used for code hashes, block hashes and discovery packets.
+//!
+//! By default hashes are computed with `tiny-keccak` (through `keccak-hash`).
+//! Enabling the `asm-keccak` feature switches to the RustCrypto implementation,
+//! which detects at runtime whether the CPU supports the ARMv8 SHA3 instructions
+//! and uses them when available, falling back to a portable implementation otherwise.
+
+use crate::H256;
+
+/// Computes the Keccak-256 hash of the given data.
+#[cfg(not(feature = "asm-keccak"))]
+pub fn keccak(data: impl AsRef<[u8]>) -> H256 {
+    keccak_hash::keccak(data.as_ref())
+}
+
+/// Computes the Keccak-256 hash of the given data.
+#[cfg(feature = "asm-keccak")]
+pub fn keccak(data: impl AsRef<[u8]>) -> H256 {
+    use sha3::{Digest, Keccak256};
+    H256(Keccak256::digest(data.as_ref()).into())
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_keccak() {
+        assert_eq!(
+            keccak([]),
+            H256::from_str("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470")
+                .unwrap()
+        );
+        assert_eq!(
+            keccak(b"abc"),
+            H256::from_str("4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45")
+                .unwrap()
+        );
+    }
+}
@@ -1,3 +1,4 @@
+pub mod hash;
 pub mod rlp;
 pub use ethereum_types::*;
 pub mod serde_utils;
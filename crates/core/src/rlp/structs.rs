@@ -66,6 +66,36 @@ impl<'a> Decoder<'a> {
         Ok((field, updated_self))
     }
 
+    /// If there is data left to decode, decodes it as the given field, else returns `None`.
+    /// Used for trailing optional fields, like those added to block headers by later forks.
+    pub fn decode_optional_field<T: RLPDecode>(
+        self,
+        name: &str,
+    ) -> Result<(Option<T>, Self), RLPDecodeError> {
+        if self.payload.is_empty() {
+            return Ok((None, self));
+        }
+        let (field, updated_self) = self.decode_field(name)?;
+        Ok((Some(field), updated_self))
+    }
+
+    /// Returns the next field without decoding it, as its RLP encoding.
+    /// Used for fields whose type is only known after reading previous ones.
+    pub fn get_encoded_item(self) -> Result<(&'a [u8], Self), RLPDecodeError> {
+        let (_, _, rest) = decode_rlp_item(self.payload)?;
+        let item = &self.payload[..self.payload.len() - rest.len()];
+        let updated_self = Self {
+            payload: rest,
+            ..self
+        };
+        Ok((item, updated_self))
+    }
+
+    /// Returns true if there are no fields left to decode.
+    pub fn is_done(&self) -> bool {
+        self.payload.is_empty()
+    }
+
     pub fn finish(self) -> Result<&'a [u8], RLPDecodeError> {
         if self.payload.is_empty() {
             Ok(self.remaining)
@@ -156,6 +186,12 @@ impl<'a> Encoder<'a> {
         self
     }
 
+    /// Stores a field that is already RLP-encoded.
+    pub fn encode_raw(mut self, encoded: &[u8]) -> Self {
+        self.temp_buf.extend_from_slice(encoded);
+        self
+    }
+
     /// Finishes encoding the struct and writes the result to the buffer.
     pub fn finish(self) {
         encode_length(self.temp_buf.len(), self.buf);
@@ -197,6 +233,41 @@ mod tests {
         assert_eq!(tuple_decode, (a, b));
     }
 
+    #[test]
+    fn test_decoder_optional_field() {
+        let mut buf = Vec::new();
+        Encoder::new(&mut buf)
+            .encode_field(&61u8)
+            .encode_optional_field::<u16>(&None)
+            .finish();
+
+        let decoder = Decoder::new(&buf).unwrap();
+        let (a, decoder): (u8, _) = decoder.decode_field("a").unwrap();
+        let (b, decoder): (Option<u16>, _) = decoder.decode_optional_field("b").unwrap();
+        let rest = decoder.finish().unwrap();
+
+        assert!(rest.is_empty());
+        assert_eq!((a, b), (61, None));
+    }
+
+    #[test]
+    fn test_raw_fields_roundtrip() {
+        let mut buf = Vec::new();
+        Encoder::new(&mut buf)
+            .encode_field(&61u8)
+            .encode_raw(&[0xc2, 1, 2])
+            .finish();
+        assert_eq!(buf, vec![0xc4, 61, 0xc2, 1, 2]);
+
+        let decoder = Decoder::new(&buf).unwrap();
+        let (a, decoder): (u8, _) = decoder.decode_field("a").unwrap();
+        let (raw, decoder) = decoder.get_encoded_item().unwrap();
+        assert!(decoder.is_done());
+        decoder.finish().unwrap();
+        assert_eq!(a, 61);
+        assert_eq!(raw, &[0xc2, 1, 2]);
+    }
+
     #[test]
     fn test_encoder_simple_struct() {
         let input = Simple { a: 61, b: 75 };
@@ -3,7 +3,13 @@ use std::collections::HashMap;
 use bytes::Bytes;
 use ethereum_types::{H256, U256};
 
-use crate::rlp::encode::RLPEncode;
+use crate::hash::keccak;
+use crate::rlp::{
+    decode::RLPDecode,
+    encode::RLPEncode,
+    error::RLPDecodeError,
+    structs::{Decoder, Encoder},
+};
 
 use super::GenesisAccount;
 
@@ -37,14 +43,31 @@ impl From<GenesisAccount> for Account {
 }
 
 fn code_hash(code: &Bytes) -> H256 {
-    keccak_hash::keccak(code.as_ref())
+    keccak(code)
 }
 
 impl RLPEncode for AccountInfo {
     fn encode(&self, buf: &mut dyn bytes::BufMut) {
-        self.code_hash.encode(buf);
-        self.balance.encode(buf);
-        self.nonce.encode(buf);
+        Encoder::new(buf)
+            .encode_field(&self.code_hash)
+            .encode_field(&self.balance)
+            .encode_field(&self.nonce)
+            .finish();
+    }
+}
+
+impl RLPDecode for AccountInfo {
+    fn decode_unfinished(rlp: &[u8]) -> Result<(Self, &[u8]), RLPDecodeError> {
+        let decoder = Decoder::new(rlp)?;
+        let (code_hash, decoder) = decoder.decode_field("code_hash")?;
+        let (balance, decoder) = decoder.decode_field("balance")?;
+        let (nonce, decoder) = decoder.decode_field("nonce")?;
+        let account_info = AccountInfo {
+            code_hash,
+            balance,
+            nonce,
+        };
+        Ok((account_info, decoder.finish()?))
     }
 }
 
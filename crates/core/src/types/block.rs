@@ -1,9 +1,55 @@
-use crate::{rlp::encode::RLPEncode, Address, H256, U256};
+use crate::{
+    hash::keccak,
+    rlp::{
+        constants::RLP_EMPTY_LIST, decode::RLPDecode, encode::RLPEncode, error::RLPDecodeError,
+        structs::Decoder, structs::Encoder,
+    },
+    Address, H256, U256,
+};
 use bytes::Bytes;
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
 
 pub type BlockNumber = u64;
 pub type Bloom = [u8; 256];
 
+/// Size of a blob carried by a blob transaction (EIP-4844).
+const BYTES_PER_BLOB: usize = 4096 * 32;
+
+/// A block on the chain, as encoded by the execution layer:
+/// the header followed by the flattened fields of the body.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Block {
+    header: BlockHeader,
+    body: Body,
+}
+
+impl RLPEncode for Block {
+    fn encode(&self, buf: &mut dyn bytes::BufMut) {
+        Encoder::new(buf)
+            .encode_field(&self.header)
+            .encode_field(&self.body.transactions)
+            .encode_field(&self.body.ommers)
+            .encode_optional_field(&self.body.withdrawals)
+            .finish();
+    }
+}
+
+impl RLPDecode for Block {
+    fn decode_unfinished(rlp: &[u8]) -> Result<(Self, &[u8]), RLPDecodeError> {
+        let decoder = Decoder::new(rlp)?;
+        let (header, decoder) = decoder.decode_field("header")?;
+        let (transactions, decoder) = decoder.decode_field("transactions")?;
+        let (ommers, decoder) = decoder.decode_field("ommers")?;
+        let (withdrawals, decoder) = decoder.decode_optional_field("withdrawals")?;
+        let body = Body {
+            transactions,
+            ommers,
+            withdrawals,
+        };
+        Ok((Block { header, body }, decoder.finish()?))
+    }
+}
+
 /// Header part of a block on the chain.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct BlockHeader {
@@ -22,35 +68,99 @@ pub struct BlockHeader {
     extra_data: Bytes,
     prev_randao: H256,
     nonce: u64,
-    base_fee_per_gas: u64,
-    withdrawals_root: H256,
-    blob_gas_used: u64,
-    excess_blob_gas: u64,
-    parent_beacon_block_root: H256,
+    // Fields added by later forks are absent from the encoding of older headers:
+    // London (EIP-1559), Shanghai (EIP-4895) and Cancun (EIP-4844, EIP-4788)
+    base_fee_per_gas: Option<u64>,
+    withdrawals_root: Option<H256>,
+    blob_gas_used: Option<u64>,
+    excess_blob_gas: Option<u64>,
+    parent_beacon_block_root: Option<H256>,
+}
+
+impl BlockHeader {
+    /// Computes the hash of the block, which is the hash of its RLP-encoded header.
+    pub fn compute_block_hash(&self) -> H256 {
+        let mut buf = Vec::new();
+        self.encode(&mut buf);
+        keccak(buf)
+    }
 }
 
 impl RLPEncode for BlockHeader {
     fn encode(&self, buf: &mut dyn bytes::BufMut) {
-        self.parent_hash.encode(buf);
-        self.ommers_hash.encode(buf);
-        self.coinbase.encode(buf);
-        self.state_root.encode(buf);
-        self.transactions_root.encode(buf);
-        self.receipt_root.encode(buf);
-        self.logs_bloom.encode(buf);
-        self.difficulty.encode(buf);
-        self.number.encode(buf);
-        self.gas_limit.encode(buf);
-        self.gas_used.encode(buf);
-        self.timestamp.encode(buf);
-        self.extra_data.encode(buf);
-        self.prev_randao.encode(buf);
-        self.nonce.encode(buf);
-        self.base_fee_per_gas.encode(buf);
-        self.withdrawals_root.encode(buf);
-        self.blob_gas_used.encode(buf);
-        self.excess_blob_gas.encode(buf);
-        self.parent_beacon_block_root.encode(buf);
+        Encoder::new(buf)
+            .encode_field(&self.parent_hash)
+            .encode_field(&self.ommers_hash)
+            .encode_field(&self.coinbase)
+            .encode_field(&self.state_root)
+            .encode_field(&self.transactions_root)
+            .encode_field(&self.receipt_root)
+            .encode_field(&self.logs_bloom)
+            .encode_field(&self.difficulty)
+            .encode_field(&self.number)
+            .encode_field(&self.gas_limit)
+            .encode_field(&self.gas_used)
+            .encode_field(&self.timestamp)
+            .encode_field(&self.extra_data)
+            .encode_field(&self.prev_randao)
+            // The nonce is always encoded as an 8-byte string
+            .encode_field(&self.nonce.to_be_bytes())
+            .encode_optional_field(&self.base_fee_per_gas)
+            .encode_optional_field(&self.withdrawals_root)
+            .encode_optional_field(&self.blob_gas_used)
+            .encode_optional_field(&self.excess_blob_gas)
+            .encode_optional_field(&self.parent_beacon_block_root)
+            .finish();
+    }
+}
+
+impl RLPDecode for BlockHeader {
+    fn decode_unfinished(rlp: &[u8]) -> Result<(Self, &[u8]), RLPDecodeError> {
+        let decoder = Decoder::new(rlp)?;
+        let (parent_hash, decoder) = decoder.decode_field("parent_hash")?;
+        let (ommers_hash, decoder) = decoder.decode_field("ommers_hash")?;
+        let (coinbase, decoder) = decoder.decode_field("coinbase")?;
+        let (state_root, decoder) = decoder.decode_field("state_root")?;
+        let (transactions_root, decoder) = decoder.decode_field("transactions_root")?;
+        let (receipt_root, decoder) = decoder.decode_field("receipt_root")?;
+        let (logs_bloom, decoder) = decoder.decode_field("logs_bloom")?;
+        let (difficulty, decoder) = decoder.decode_field("difficulty")?;
+        let (number, decoder) = decoder.decode_field("number")?;
+        let (gas_limit, decoder) = decoder.decode_field("gas_limit")?;
+        let (gas_used, decoder) = decoder.decode_field("gas_used")?;
+        let (timestamp, decoder) = decoder.decode_field("timestamp")?;
+        let (extra_data, decoder) = decoder.decode_field("extra_data")?;
+        let (prev_randao, decoder) = decoder.decode_field("prev_randao")?;
+        let (nonce, decoder): ([u8; 8], _) = decoder.decode_field("nonce")?;
+        let (base_fee_per_gas, decoder) = decoder.decode_optional_field("base_fee_per_gas")?;
+        let (withdrawals_root, decoder) = decoder.decode_optional_field("withdrawals_root")?;
+        let (blob_gas_used, decoder) = decoder.decode_optional_field("blob_gas_used")?;
+        let (excess_blob_gas, decoder) = decoder.decode_optional_field("excess_blob_gas")?;
+        let (parent_beacon_block_root, decoder) =
+            decoder.decode_optional_field("parent_beacon_block_root")?;
+        let header = BlockHeader {
+            parent_hash,
+            ommers_hash,
+            coinbase,
+            state_root,
+            transactions_root,
+            receipt_root,
+            logs_bloom,
+            difficulty,
+            number,
+            gas_limit,
+            gas_used,
+            timestamp,
+            extra_data,
+            prev_randao,
+            nonce: u64::from_be_bytes(nonce),
+            base_fee_per_gas,
+            withdrawals_root,
+            blob_gas_used,
+            excess_blob_gas,
+            parent_beacon_block_root,
+        };
+        Ok((header, decoder.finish()?))
     }
 }
 
@@ -59,14 +169,32 @@ impl RLPEncode for BlockHeader {
 pub struct Body {
     transactions: Vec<Transaction>,
     ommers: Vec<BlockHeader>,
-    withdrawals: Vec<Withdrawal>,
+    // Only present after Shanghai
+    withdrawals: Option<Vec<Withdrawal>>,
 }
 
 impl RLPEncode for Body {
     fn encode(&self, buf: &mut dyn bytes::BufMut) {
-        self.transactions.encode(buf);
-        self.ommers.encode(buf);
-        self.withdrawals.encode(buf);
+        Encoder::new(buf)
+            .encode_field(&self.transactions)
+            .encode_field(&self.ommers)
+            .encode_optional_field(&self.withdrawals)
+            .finish();
+    }
+}
+
+impl RLPDecode for Body {
+    fn decode_unfinished(rlp: &[u8]) -> Result<(Self, &[u8]), RLPDecodeError> {
+        let decoder = Decoder::new(rlp)?;
+        let (transactions, decoder) = decoder.decode_field("transactions")?;
+        let (ommers, decoder) = decoder.decode_field("ommers")?;
+        let (withdrawals, decoder) = decoder.decode_optional_field("withdrawals")?;
+        let body = Body {
+            transactions,
+            ommers,
+            withdrawals,
+        };
+        Ok((body, decoder.finish()?))
     }
 }
 
@@ -80,28 +208,283 @@ pub struct Withdrawal {
 
 impl RLPEncode for Withdrawal {
     fn encode(&self, buf: &mut dyn bytes::BufMut) {
-        self.index.encode(buf);
-        self.validator_index.encode(buf);
-        self.address.encode(buf);
-        self.amount.encode(buf);
+        Encoder::new(buf)
+            .encode_field(&self.index)
+            .encode_field(&self.validator_index)
+            .encode_field(&self.address)
+            .encode_field(&self.amount)
+            .finish();
+    }
+}
+
+impl RLPDecode for Withdrawal {
+    fn decode_unfinished(rlp: &[u8]) -> Result<(Self, &[u8]), RLPDecodeError> {
+        let decoder = Decoder::new(rlp)?;
+        let (index, decoder) = decoder.decode_field("index")?;
+        let (validator_index, decoder) = decoder.decode_field("validator_index")?;
+        let (address, decoder) = decoder.decode_field("address")?;
+        let (amount, decoder) = decoder.decode_field("amount")?;
+        let withdrawal = Withdrawal {
+            index,
+            validator_index,
+            address,
+            amount,
+        };
+        Ok((withdrawal, decoder.finish()?))
     }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Transaction {
     LegacyTransaction(LegacyTransaction),
+    EIP2930Transaction(EIP2930Transaction),
     EIP1559Transaction(EIP1559Transaction),
+    EIP4844Transaction(EIP4844Transaction),
+}
+
+impl Transaction {
+    /// Type of the transaction as defined by EIP-2718.
+    pub fn tx_type(&self) -> u8 {
+        match self {
+            Transaction::LegacyTransaction(_) => 0x00,
+            Transaction::EIP2930Transaction(_) => 0x01,
+            Transaction::EIP1559Transaction(_) => 0x02,
+            Transaction::EIP4844Transaction(_) => 0x03,
+        }
+    }
+
+    /// Encodes the transaction in its canonical form, as used for hashing and by
+    /// `eth_sendRawTransaction`: legacy transactions as an RLP list, and typed
+    /// transactions as the transaction type followed by its RLP payload.
+    pub fn encode_canonical(&self, buf: &mut dyn bytes::BufMut) {
+        if !matches!(self, Transaction::LegacyTransaction(_)) {
+            buf.put_u8(self.tx_type());
+        }
+        match self {
+            Transaction::LegacyTransaction(t) => t.encode(buf),
+            Transaction::EIP2930Transaction(t) => t.encode(buf),
+            Transaction::EIP1559Transaction(t) => t.encode(buf),
+            Transaction::EIP4844Transaction(t) => t.encode(buf),
+        }
+    }
+
+    /// Decodes a transaction from its canonical form. See [`Transaction::encode_canonical`].
+    /// Blob transactions are also accepted in the network form used to gossip them,
+    /// in which case the blobs, commitments and proofs are checked and dropped.
+    pub fn decode_canonical(bytes: &[u8]) -> Result<Self, RLPDecodeError> {
+        let (tx_type, payload) = bytes.split_first().ok_or(RLPDecodeError::InvalidLength)?;
+        if *tx_type >= RLP_EMPTY_LIST {
+            return Ok(Transaction::LegacyTransaction(LegacyTransaction::decode(
+                bytes,
+            )?));
+        }
+        if *tx_type == 0x03 {
+            return Ok(Transaction::EIP4844Transaction(
+                EIP4844Transaction::decode_network_form(payload)?,
+            ));
+        }
+        Self::decode_typed(*tx_type, payload)
+    }
+
+    /// Decodes the RLP payload of a typed transaction (EIP-2718).
+    fn decode_typed(tx_type: u8, payload: &[u8]) -> Result<Self, RLPDecodeError> {
+        let tx = match tx_type {
+            0x01 => Transaction::EIP2930Transaction(EIP2930Transaction::decode(payload)?),
+            0x02 => Transaction::EIP1559Transaction(EIP1559Transaction::decode(payload)?),
+            0x03 => Transaction::EIP4844Transaction(EIP4844Transaction::decode(payload)?),
+            ty => {
+                return Err(RLPDecodeError::Custom(format!(
+                    "Unsupported transaction type: {ty}"
+                )))
+            }
+        };
+        Ok(tx)
+    }
+
+    /// Computes the transaction hash, which is the hash of its canonical encoding.
+    pub fn compute_hash(&self) -> H256 {
+        let mut buf = Vec::new();
+        self.encode_canonical(&mut buf);
+        keccak(buf)
+    }
+
+    /// Recovers the address of the account that signed the transaction.
+    pub fn sender(&self) -> Result<Address, SignatureError> {
+        let mut payload = Vec::new();
+        let (r, s, recovery_id) = match self {
+            Transaction::LegacyTransaction(t) => {
+                let v = u64::try_from(t.v).map_err(|_| SignatureError::InvalidRecoveryId)?;
+                let recovery_id = match v {
+                    27 | 28 => {
+                        t.encode_signing_payload(&mut payload, None);
+                        v - 27
+                    }
+                    // EIP-155: v = chain_id * 2 + 35 + recovery_id
+                    v if v >= 35 => {
+                        t.encode_signing_payload(&mut payload, Some((v - 35) / 2));
+                        (v - 35) % 2
+                    }
+                    _ => return Err(SignatureError::InvalidRecoveryId),
+                };
+                (t.r, t.s, recovery_id as u8)
+            }
+            // Typed transactions sign their type followed by every field but the signature
+            Transaction::EIP2930Transaction(t) => {
+                payload.push(self.tx_type());
+                t.encode_signing_payload(&mut payload);
+                (t.signature_r, t.signature_s, t.signature_y_parity as u8)
+            }
+            Transaction::EIP1559Transaction(t) => {
+                payload.push(self.tx_type());
+                t.encode_signing_payload(&mut payload);
+                (t.signature_r, t.signature_s, t.signature_y_parity as u8)
+            }
+            Transaction::EIP4844Transaction(t) => {
+                payload.push(self.tx_type());
+                t.encode_signing_payload(&mut payload);
+                (t.signature_r, t.signature_s, t.signature_y_parity as u8)
+            }
+        };
+        recover_address(r, s, recovery_id, keccak(payload))
+    }
+}
+
+/// A value along with its hash, computed once when the value is sealed so that
+/// code that needs the hash repeatedly doesn't re-encode and re-hash it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sealed<T> {
+    inner: T,
+    hash: H256,
+}
+
+pub type SealedHeader = Sealed<BlockHeader>;
+pub type SealedTransaction = Sealed<Transaction>;
+
+impl<T> Sealed<T> {
+    pub fn hash(&self) -> H256 {
+        self.hash
+    }
+
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T> std::ops::Deref for Sealed<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl SealedHeader {
+    pub fn seal(header: BlockHeader) -> Self {
+        let hash = header.compute_block_hash();
+        Self {
+            inner: header,
+            hash,
+        }
+    }
+}
+
+impl SealedTransaction {
+    pub fn seal(tx: Transaction) -> Self {
+        let hash = tx.compute_hash();
+        Self { inner: tx, hash }
+    }
+}
+
+impl<T: RLPEncode> RLPEncode for Sealed<T> {
+    fn encode(&self, buf: &mut dyn bytes::BufMut) {
+        self.inner.encode(buf)
+    }
+}
+
+impl RLPDecode for SealedHeader {
+    fn decode_unfinished(rlp: &[u8]) -> Result<(Self, &[u8]), RLPDecodeError> {
+        let (header, rest) = BlockHeader::decode_unfinished(rlp)?;
+        // The header hash is the hash of its encoding, so it can be taken
+        // straight from the decoded bytes without encoding it again
+        let hash = keccak(&rlp[..rlp.len() - rest.len()]);
+        Ok((
+            Self {
+                inner: header,
+                hash,
+            },
+            rest,
+        ))
+    }
 }
 
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum SignatureError {
+    #[error("Invalid signature recovery id")]
+    InvalidRecoveryId,
+    #[error("Invalid signature")]
+    InvalidSignature,
+}
+
+/// Recovers the address whose key produced the given signature over `message_hash`.
+fn recover_address(
+    r: U256,
+    s: U256,
+    recovery_id: u8,
+    message_hash: H256,
+) -> Result<Address, SignatureError> {
+    let mut r_bytes = [0; 32];
+    let mut s_bytes = [0; 32];
+    r.to_big_endian(&mut r_bytes);
+    s.to_big_endian(&mut s_bytes);
+    let signature =
+        Signature::from_scalars(r_bytes, s_bytes).map_err(|_| SignatureError::InvalidSignature)?;
+    let recovery_id =
+        RecoveryId::from_byte(recovery_id).ok_or(SignatureError::InvalidRecoveryId)?;
+    let public_key =
+        VerifyingKey::recover_from_prehash(message_hash.as_bytes(), &signature, recovery_id)
+            .map_err(|_| SignatureError::InvalidSignature)?;
+    // The address is the last 20 bytes of the hash of the uncompressed public key,
+    // without its leading SEC1 tag byte
+    let public_key = public_key.to_encoded_point(false);
+    let hash = keccak(&public_key.as_bytes()[1..]);
+    Ok(Address::from_slice(&hash.as_bytes()[12..]))
+}
+
+/// Transactions are encoded as they appear in a block body:
+/// legacy transactions as an RLP list, and typed transactions (EIP-2718)
+/// as an RLP string containing the transaction type followed by its payload.
 impl RLPEncode for Transaction {
     fn encode(&self, buf: &mut dyn bytes::BufMut) {
         match self {
             Transaction::LegacyTransaction(t) => t.encode(buf),
-            Transaction::EIP1559Transaction(t) => t.encode(buf),
+            _ => {
+                let mut typed_tx = Vec::new();
+                self.encode_canonical(&mut typed_tx);
+                typed_tx.as_slice().encode(buf)
+            }
         };
     }
 }
 
+impl RLPDecode for Transaction {
+    fn decode_unfinished(rlp: &[u8]) -> Result<(Self, &[u8]), RLPDecodeError> {
+        let first_byte = *rlp.first().ok_or(RLPDecodeError::InvalidLength)?;
+        if first_byte >= RLP_EMPTY_LIST {
+            let (tx, rest) = LegacyTransaction::decode_unfinished(rlp)?;
+            return Ok((Transaction::LegacyTransaction(tx), rest));
+        }
+        let (typed_tx, rest) = Bytes::decode_unfinished(rlp)?;
+        let (tx_type, payload) = typed_tx
+            .split_first()
+            .ok_or(RLPDecodeError::InvalidLength)?;
+        Ok((Transaction::decode_typed(*tx_type, payload)?, rest))
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct LegacyTransaction {
     nonce: U256,
@@ -117,15 +500,141 @@ pub struct LegacyTransaction {
 
 impl RLPEncode for LegacyTransaction {
     fn encode(&self, buf: &mut dyn bytes::BufMut) {
-        self.nonce.encode(buf);
-        self.gas_price.encode(buf);
-        self.gas.encode(buf);
-        self.to.encode(buf);
-        self.value.encode(buf);
-        self.data.encode(buf);
-        self.v.encode(buf);
-        self.r.encode(buf);
-        self.s.encode(buf);
+        Encoder::new(buf)
+            .encode_field(&self.nonce)
+            .encode_field(&self.gas_price)
+            .encode_field(&self.gas)
+            .encode_field(&self.to)
+            .encode_field(&self.value)
+            .encode_field(&self.data)
+            .encode_field(&self.v)
+            .encode_field(&self.r)
+            .encode_field(&self.s)
+            .finish();
+    }
+}
+
+impl LegacyTransaction {
+    /// Encodes the fields covered by the signature. Replay protected transactions
+    /// (EIP-155) also commit to the chain id.
+    fn encode_signing_payload(&self, buf: &mut dyn bytes::BufMut, chain_id: Option<u64>) {
+        let encoder = Encoder::new(buf)
+            .encode_field(&self.nonce)
+            .encode_field(&self.gas_price)
+            .encode_field(&self.gas)
+            .encode_field(&self.to)
+            .encode_field(&self.value)
+            .encode_field(&self.data);
+        match chain_id {
+            Some(chain_id) => encoder
+                .encode_field(&chain_id)
+                .encode_field(&0u8)
+                .encode_field(&0u8)
+                .finish(),
+            None => encoder.finish(),
+        }
+    }
+}
+
+impl RLPDecode for LegacyTransaction {
+    fn decode_unfinished(rlp: &[u8]) -> Result<(Self, &[u8]), RLPDecodeError> {
+        let decoder = Decoder::new(rlp)?;
+        let (nonce, decoder) = decoder.decode_field("nonce")?;
+        let (gas_price, decoder) = decoder.decode_field("gas_price")?;
+        let (gas, decoder) = decoder.decode_field("gas")?;
+        let (to, decoder) = decoder.decode_field("to")?;
+        let (value, decoder) = decoder.decode_field("value")?;
+        let (data, decoder) = decoder.decode_field("data")?;
+        let (v, decoder) = decoder.decode_field("v")?;
+        let (r, decoder) = decoder.decode_field("r")?;
+        let (s, decoder) = decoder.decode_field("s")?;
+        let tx = LegacyTransaction {
+            nonce,
+            gas_price,
+            gas,
+            to,
+            value,
+            data,
+            v,
+            r,
+            s,
+        };
+        Ok((tx, decoder.finish()?))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EIP2930Transaction {
+    chain_id: u64,
+    signer_nonce: U256,
+    gas_price: u64,
+    gas_limit: u64,
+    destination: Address,
+    amount: U256,
+    payload: Bytes,
+    access_list: Vec<(Address, Vec<H256>)>,
+    signature_y_parity: bool,
+    signature_r: U256,
+    signature_s: U256,
+}
+
+impl RLPEncode for EIP2930Transaction {
+    fn encode(&self, buf: &mut dyn bytes::BufMut) {
+        self.encode_unsigned_fields(Encoder::new(buf))
+            .encode_field(&self.signature_y_parity)
+            .encode_field(&self.signature_r)
+            .encode_field(&self.signature_s)
+            .finish();
+    }
+}
+
+impl EIP2930Transaction {
+    /// Encodes the fields covered by the signature, i.e. all of them but the signature itself.
+    fn encode_signing_payload(&self, buf: &mut dyn bytes::BufMut) {
+        self.encode_unsigned_fields(Encoder::new(buf)).finish();
+    }
+
+    fn encode_unsigned_fields<'a>(&self, encoder: Encoder<'a>) -> Encoder<'a> {
+        encoder
+            .encode_field(&self.chain_id)
+            .encode_field(&self.signer_nonce)
+            .encode_field(&self.gas_price)
+            .encode_field(&self.gas_limit)
+            .encode_field(&self.destination)
+            .encode_field(&self.amount)
+            .encode_field(&self.payload)
+            .encode_field(&self.access_list)
+    }
+}
+
+impl RLPDecode for EIP2930Transaction {
+    fn decode_unfinished(rlp: &[u8]) -> Result<(Self, &[u8]), RLPDecodeError> {
+        let decoder = Decoder::new(rlp)?;
+        let (chain_id, decoder) = decoder.decode_field("chain_id")?;
+        let (signer_nonce, decoder) = decoder.decode_field("signer_nonce")?;
+        let (gas_price, decoder) = decoder.decode_field("gas_price")?;
+        let (gas_limit, decoder) = decoder.decode_field("gas_limit")?;
+        let (destination, decoder) = decoder.decode_field("destination")?;
+        let (amount, decoder) = decoder.decode_field("amount")?;
+        let (payload, decoder) = decoder.decode_field("payload")?;
+        let (access_list, decoder) = decoder.decode_field("access_list")?;
+        let (signature_y_parity, decoder) = decoder.decode_field("signature_y_parity")?;
+        let (signature_r, decoder) = decoder.decode_field("signature_r")?;
+        let (signature_s, decoder) = decoder.decode_field("signature_s")?;
+        let tx = EIP2930Transaction {
+            chain_id,
+            signer_nonce,
+            gas_price,
+            gas_limit,
+            destination,
+            amount,
+            payload,
+            access_list,
+            signature_y_parity,
+            signature_r,
+            signature_s,
+        };
+        Ok((tx, decoder.finish()?))
     }
 }
 
@@ -147,17 +656,809 @@ pub struct EIP1559Transaction {
 
 impl RLPEncode for EIP1559Transaction {
     fn encode(&self, buf: &mut dyn bytes::BufMut) {
-        self.chain_id.encode(buf);
-        self.signer_nonce.encode(buf);
-        self.max_priority_fee_per_gas.encode(buf);
-        self.max_fee_per_gas.encode(buf);
-        self.gas_limit.encode(buf);
-        self.destination.encode(buf);
-        self.amount.encode(buf);
-        self.payload.encode(buf);
-        self.access_list.encode(buf);
-        self.signature_y_parity.encode(buf);
-        self.signature_r.encode(buf);
-        self.signature_s.encode(buf);
+        self.encode_unsigned_fields(Encoder::new(buf))
+            .encode_field(&self.signature_y_parity)
+            .encode_field(&self.signature_r)
+            .encode_field(&self.signature_s)
+            .finish();
+    }
+}
+
+impl EIP1559Transaction {
+    /// Encodes the fields covered by the signature, i.e. all of them but the signature itself.
+    fn encode_signing_payload(&self, buf: &mut dyn bytes::BufMut) {
+        self.encode_unsigned_fields(Encoder::new(buf)).finish();
+    }
+
+    fn encode_unsigned_fields<'a>(&self, encoder: Encoder<'a>) -> Encoder<'a> {
+        encoder
+            .encode_field(&self.chain_id)
+            .encode_field(&self.signer_nonce)
+            .encode_field(&self.max_priority_fee_per_gas)
+            .encode_field(&self.max_fee_per_gas)
+            .encode_field(&self.gas_limit)
+            .encode_field(&self.destination)
+            .encode_field(&self.amount)
+            .encode_field(&self.payload)
+            .encode_field(&self.access_list)
+    }
+}
+
+impl RLPDecode for EIP1559Transaction {
+    fn decode_unfinished(rlp: &[u8]) -> Result<(Self, &[u8]), RLPDecodeError> {
+        let decoder = Decoder::new(rlp)?;
+        let (chain_id, decoder) = decoder.decode_field("chain_id")?;
+        let (signer_nonce, decoder) = decoder.decode_field("signer_nonce")?;
+        let (max_priority_fee_per_gas, decoder) =
+            decoder.decode_field("max_priority_fee_per_gas")?;
+        let (max_fee_per_gas, decoder) = decoder.decode_field("max_fee_per_gas")?;
+        let (gas_limit, decoder) = decoder.decode_field("gas_limit")?;
+        let (destination, decoder) = decoder.decode_field("destination")?;
+        let (amount, decoder) = decoder.decode_field("amount")?;
+        let (payload, decoder) = decoder.decode_field("payload")?;
+        let (access_list, decoder) = decoder.decode_field("access_list")?;
+        let (signature_y_parity, decoder) = decoder.decode_field("signature_y_parity")?;
+        let (signature_r, decoder) = decoder.decode_field("signature_r")?;
+        let (signature_s, decoder) = decoder.decode_field("signature_s")?;
+        let tx = EIP1559Transaction {
+            chain_id,
+            signer_nonce,
+            max_priority_fee_per_gas,
+            max_fee_per_gas,
+            gas_limit,
+            destination,
+            amount,
+            payload,
+            access_list,
+            signature_y_parity,
+            signature_r,
+            signature_s,
+        };
+        Ok((tx, decoder.finish()?))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EIP4844Transaction {
+    chain_id: u64,
+    signer_nonce: U256,
+    max_priority_fee_per_gas: u64,
+    max_fee_per_gas: u64,
+    gas_limit: u64,
+    // Blob transactions can't create contracts, so the destination is always present
+    destination: Address,
+    amount: U256,
+    payload: Bytes,
+    access_list: Vec<(Address, Vec<H256>)>,
+    max_fee_per_blob_gas: U256,
+    blob_versioned_hashes: Vec<H256>,
+    signature_y_parity: bool,
+    signature_r: U256,
+    signature_s: U256,
+}
+
+impl RLPEncode for EIP4844Transaction {
+    fn encode(&self, buf: &mut dyn bytes::BufMut) {
+        self.encode_unsigned_fields(Encoder::new(buf))
+            .encode_field(&self.signature_y_parity)
+            .encode_field(&self.signature_r)
+            .encode_field(&self.signature_s)
+            .finish();
+    }
+}
+
+impl EIP4844Transaction {
+    /// Encodes the fields covered by the signature, i.e. all of them but the signature itself.
+    fn encode_signing_payload(&self, buf: &mut dyn bytes::BufMut) {
+        self.encode_unsigned_fields(Encoder::new(buf)).finish();
+    }
+
+    fn encode_unsigned_fields<'a>(&self, encoder: Encoder<'a>) -> Encoder<'a> {
+        encoder
+            .encode_field(&self.chain_id)
+            .encode_field(&self.signer_nonce)
+            .encode_field(&self.max_priority_fee_per_gas)
+            .encode_field(&self.max_fee_per_gas)
+            .encode_field(&self.gas_limit)
+            .encode_field(&self.destination)
+            .encode_field(&self.amount)
+            .encode_field(&self.payload)
+            .encode_field(&self.access_list)
+            .encode_field(&self.max_fee_per_blob_gas)
+            .encode_field(&self.blob_versioned_hashes)
+    }
+
+    /// Decodes the payload of a blob transaction, either on its own or in the network
+    /// form defined by EIP-4844: `rlp([tx_payload_body, blobs, commitments, proofs])`.
+    /// The sidecar is only checked to hold one blob, commitment and proof per versioned
+    /// hash, KZG proofs aren't verified.
+    fn decode_network_form(payload: &[u8]) -> Result<Self, RLPDecodeError> {
+        let (first_field, _) = Decoder::new(payload)?.get_encoded_item()?;
+        // In the canonical form the first field is the chain id, never a list
+        if first_field
+            .first()
+            .is_some_and(|byte| *byte < RLP_EMPTY_LIST)
+        {
+            return Self::decode(payload);
+        }
+        let decoder = Decoder::new(payload)?;
+        let (tx, decoder) = decoder.decode_field::<EIP4844Transaction>("tx_payload_body")?;
+        let (blobs, decoder) = decoder.decode_field::<Vec<Bytes>>("blobs")?;
+        let (commitments, decoder) = decoder.decode_field::<Vec<[u8; 48]>>("commitments")?;
+        let (proofs, decoder) = decoder.decode_field::<Vec<[u8; 48]>>("proofs")?;
+        let rest = decoder.finish()?;
+        if !rest.is_empty() {
+            return Err(RLPDecodeError::InvalidLength);
+        }
+
+        let blob_count = tx.blob_versioned_hashes.len();
+        if blobs.len() != blob_count
+            || commitments.len() != blob_count
+            || proofs.len() != blob_count
+        {
+            return Err(RLPDecodeError::Custom(format!(
+                "Expected {blob_count} blobs, commitments and proofs, got {}, {} and {}",
+                blobs.len(),
+                commitments.len(),
+                proofs.len()
+            )));
+        }
+        if blobs.iter().any(|blob| blob.len() != BYTES_PER_BLOB) {
+            return Err(RLPDecodeError::Custom(format!(
+                "Blobs must be {BYTES_PER_BLOB} bytes long"
+            )));
+        }
+        Ok(tx)
+    }
+}
+
+impl RLPDecode for EIP4844Transaction {
+    fn decode_unfinished(rlp: &[u8]) -> Result<(Self, &[u8]), RLPDecodeError> {
+        let decoder = Decoder::new(rlp)?;
+        let (chain_id, decoder) = decoder.decode_field("chain_id")?;
+        let (signer_nonce, decoder) = decoder.decode_field("signer_nonce")?;
+        let (max_priority_fee_per_gas, decoder) =
+            decoder.decode_field("max_priority_fee_per_gas")?;
+        let (max_fee_per_gas, decoder) = decoder.decode_field("max_fee_per_gas")?;
+        let (gas_limit, decoder) = decoder.decode_field("gas_limit")?;
+        let (destination, decoder) = decoder.decode_field("destination")?;
+        let (amount, decoder) = decoder.decode_field("amount")?;
+        let (payload, decoder) = decoder.decode_field("payload")?;
+        let (access_list, decoder) = decoder.decode_field("access_list")?;
+        let (max_fee_per_blob_gas, decoder) = decoder.decode_field("max_fee_per_blob_gas")?;
+        let (blob_versioned_hashes, decoder) = decoder.decode_field("blob_versioned_hashes")?;
+        let (signature_y_parity, decoder) = decoder.decode_field("signature_y_parity")?;
+        let (signature_r, decoder) = decoder.decode_field("signature_r")?;
+        let (signature_s, decoder) = decoder.decode_field("signature_s")?;
+        let tx = EIP4844Transaction {
+            chain_id,
+            signer_nonce,
+            max_priority_fee_per_gas,
+            max_fee_per_gas,
+            gas_limit,
+            destination,
+            amount,
+            payload,
+            access_list,
+            max_fee_per_blob_gas,
+            blob_versioned_hashes,
+            signature_y_parity,
+            signature_r,
+            signature_s,
+        };
+        Ok((tx, decoder.finish()?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs::File, io::BufReader, str::FromStr};
+
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::types::Receipt;
+
+    #[derive(Deserialize)]
+    struct Fixture {
+        name: String,
+        rlp: String,
+    }
+
+    #[derive(Deserialize)]
+    struct TransactionFixture {
+        #[serde(flatten)]
+        fixture: Fixture,
+        hash: H256,
+        sender: Address,
+    }
+
+    #[derive(Deserialize)]
+    struct HeaderFixture {
+        #[serde(flatten)]
+        fixture: Fixture,
+        hash: H256,
+    }
+
+    #[derive(Deserialize)]
+    struct Fixtures {
+        blocks: Vec<Fixture>,
+        headers: Vec<HeaderFixture>,
+        transactions: Vec<TransactionFixture>,
+        receipts: Vec<Fixture>,
+    }
+
+    fn read_fixtures() -> Fixtures {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../test_data/rlp_fixtures.json"
+        );
+        let file = File::open(path).expect("Failed to open RLP fixtures file");
+        serde_json::from_reader(BufReader::new(file)).expect("Failed to deserialize RLP fixtures")
+    }
+
+    fn assert_roundtrip<T: RLPEncode + RLPDecode>(fixture: &Fixture) {
+        let rlp = hex::decode(fixture.rlp.trim_start_matches("0x")).unwrap();
+        let decoded = T::decode(&rlp)
+            .unwrap_or_else(|err| panic!("Failed to decode fixture '{}': {err}", fixture.name));
+        let mut encoded = Vec::new();
+        decoded.encode(&mut encoded);
+        assert_eq!(
+            encoded, rlp,
+            "Re-encoding fixture '{}' differs",
+            fixture.name
+        );
+    }
+
+    #[test]
+    fn block_fixtures_roundtrip() {
+        for fixture in read_fixtures().blocks {
+            assert_roundtrip::<Block>(&fixture);
+        }
+    }
+
+    #[test]
+    fn transaction_fixtures_roundtrip() {
+        // Transaction fixtures hold the canonical encoding, their block body
+        // encoding is covered by the block fixtures
+        for TransactionFixture { fixture, .. } in read_fixtures().transactions {
+            let rlp = hex::decode(fixture.rlp.trim_start_matches("0x")).unwrap();
+            let decoded = Transaction::decode_canonical(&rlp)
+                .unwrap_or_else(|err| panic!("Failed to decode fixture '{}': {err}", fixture.name));
+            let mut encoded = Vec::new();
+            decoded.encode_canonical(&mut encoded);
+            assert_eq!(
+                encoded, rlp,
+                "Re-encoding fixture '{}' differs",
+                fixture.name
+            );
+        }
+    }
+
+    #[test]
+    fn header_fixtures_roundtrip() {
+        for HeaderFixture { fixture, hash } in read_fixtures().headers {
+            assert_roundtrip::<BlockHeader>(&fixture);
+            let rlp = hex::decode(fixture.rlp.trim_start_matches("0x")).unwrap();
+            let header = BlockHeader::decode(&rlp).unwrap();
+            assert_eq!(
+                header.compute_block_hash(),
+                hash,
+                "Hash of '{}'",
+                fixture.name
+            );
+        }
+    }
+
+    #[test]
+    fn receipt_fixtures_roundtrip() {
+        // Receipt fixtures hold the canonical encoding, typed receipts are
+        // prefixed with their transaction type (EIP-2718)
+        for fixture in read_fixtures().receipts {
+            let rlp = hex::decode(fixture.rlp.trim_start_matches("0x")).unwrap();
+            let decoded = Receipt::decode_canonical(&rlp)
+                .unwrap_or_else(|err| panic!("Failed to decode fixture '{}': {err}", fixture.name));
+            let mut encoded = Vec::new();
+            decoded.encode_canonical(&mut encoded);
+            assert_eq!(
+                encoded, rlp,
+                "Re-encoding fixture '{}' differs",
+                fixture.name
+            );
+            // As part of a list, typed receipts are wrapped in an RLP string
+            let mut wrapped = Vec::new();
+            decoded.encode(&mut wrapped);
+            assert_eq!(Receipt::decode(&wrapped).unwrap(), decoded);
+        }
+    }
+
+    #[test]
+    fn decode_block_fixture_fields() {
+        let fixture = &read_fixtures().blocks[1];
+        let rlp = hex::decode(fixture.rlp.trim_start_matches("0x")).unwrap();
+        let block = Block::decode(&rlp).unwrap();
+
+        assert_eq!(block.header.number, 1);
+        assert_eq!(block.header.gas_limit, 0xff112233445566);
+        assert_eq!(block.header.timestamp, 1000);
+        assert_eq!(block.header.nonce, 0);
+        assert_eq!(block.header.base_fee_per_gas, Some(10));
+        assert_eq!(block.header.blob_gas_used, Some(0));
+        assert_eq!(block.header.parent_beacon_block_root, Some(H256::zero()));
+        assert_eq!(block.body.transactions.len(), 1);
+        assert!(block.body.ommers.is_empty());
+        assert_eq!(block.body.withdrawals, Some(vec![]));
+    }
+
+    #[test]
+    fn compute_block_hash_of_fixtures() {
+        let fixtures = read_fixtures();
+        let expected_hashes = [
+            "4b752ac4fc8dadaa1b82eb79c94f60e0daedf71cf36ac1e441bff7f2c227e4c7",
+            "294115a365c9113463fc5f85f09a77deb7b4fc56bc9d2b9b92dcaf8d04b2f26f",
+            "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3",
+        ];
+        for (fixture, expected_hash) in fixtures.blocks.iter().zip(expected_hashes) {
+            let rlp = hex::decode(fixture.rlp.trim_start_matches("0x")).unwrap();
+            let block = Block::decode(&rlp).unwrap();
+            assert_eq!(
+                block.header.compute_block_hash(),
+                H256::from_str(expected_hash).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn sealed_header_hash() {
+        let fixture = &read_fixtures().blocks[1];
+        let rlp = hex::decode(fixture.rlp.trim_start_matches("0x")).unwrap();
+        let block = Block::decode(&rlp).unwrap();
+        let mut header_rlp = Vec::new();
+        block.header.encode(&mut header_rlp);
+
+        let sealed = SealedHeader::decode(&header_rlp).unwrap();
+        assert_eq!(sealed.hash(), block.header.compute_block_hash());
+        assert_eq!(sealed, SealedHeader::seal(block.header.clone()));
+        assert_eq!(sealed.number, 1);
+    }
+
+    #[test]
+    fn transaction_hash_and_sender() {
+        for TransactionFixture {
+            fixture,
+            hash,
+            sender,
+        } in read_fixtures().transactions
+        {
+            let rlp = hex::decode(fixture.rlp.trim_start_matches("0x")).unwrap();
+            let tx = Transaction::decode_canonical(&rlp).unwrap();
+            assert_eq!(tx.compute_hash(), hash, "Hash of '{}'", fixture.name);
+            assert_eq!(tx.sender().unwrap(), sender, "Sender of '{}'", fixture.name);
+        }
+    }
+
+    #[test]
+    fn block_transaction_sender() {
+        let fixtures = read_fixtures();
+        let rlp = hex::decode(fixtures.blocks[1].rlp.trim_start_matches("0x")).unwrap();
+        let block = Block::decode(&rlp).unwrap();
+        let tx = &block.body.transactions[0];
+        assert_eq!(
+            tx.sender().unwrap(),
+            Address::from_str("a94f5374fce5edbc8e2a8697c15331677e6ebf0b").unwrap()
+        );
+    }
+
+    #[test]
+    fn decode_header_fixtures_across_forks() {
+        let headers: Vec<_> = read_fixtures()
+            .headers
+            .iter()
+            .map(|fixture| {
+                let rlp = hex::decode(fixture.fixture.rlp.trim_start_matches("0x")).unwrap();
+                BlockHeader::decode(&rlp).unwrap()
+            })
+            .collect();
+
+        let [pre_london, london, cancun] = headers.as_slice() else {
+            panic!("Expected three header fixtures");
+        };
+        assert_eq!(pre_london.number, 11117104);
+        assert_eq!(pre_london.base_fee_per_gas, None);
+        assert_eq!(pre_london.withdrawals_root, None);
+
+        assert!(london.base_fee_per_gas.is_some());
+        assert_eq!(london.withdrawals_root, None);
+
+        assert_eq!(cancun.number, 19449567);
+        assert!(cancun.withdrawals_root.is_some());
+        assert!(cancun.blob_gas_used.is_some());
+        assert!(cancun.excess_blob_gas.is_some());
+        assert!(cancun.parent_beacon_block_root.is_some());
+    }
+
+    #[test]
+    fn body_with_every_transaction_type_roundtrip() {
+        let fixtures = read_fixtures();
+        let transactions: Vec<_> = fixtures
+            .transactions
+            .iter()
+            .map(|tx| {
+                let rlp = hex::decode(tx.fixture.rlp.trim_start_matches("0x")).unwrap();
+                Transaction::decode_canonical(&rlp).unwrap()
+            })
+            .collect();
+        let tx_types: Vec<_> = transactions.iter().map(|tx| tx.tx_type()).collect();
+        assert_eq!(tx_types, [0x00, 0x00, 0x00, 0x01, 0x01, 0x02, 0x03]);
+
+        let ommer_rlp = hex::decode(fixtures.headers[0].fixture.rlp.trim_start_matches("0x"));
+        let body = Body {
+            transactions,
+            ommers: vec![BlockHeader::decode(&ommer_rlp.unwrap()).unwrap()],
+            withdrawals: Some(vec![Withdrawal {
+                index: 1,
+                validator_index: 2,
+                address: Address::repeat_byte(3),
+                amount: U256::from(4),
+            }]),
+        };
+        let mut encoded = Vec::new();
+        body.encode(&mut encoded);
+        assert_eq!(Body::decode(&encoded).unwrap(), body);
+    }
+
+    #[test]
+    fn eip1559_transaction_roundtrip() {
+        let tx = Transaction::EIP1559Transaction(EIP1559Transaction {
+            chain_id: 1,
+            signer_nonce: U256::from(7),
+            max_priority_fee_per_gas: 2_000_000_000,
+            max_fee_per_gas: 30_000_000_000,
+            gas_limit: 21000,
+            destination: Address::repeat_byte(0x35),
+            amount: 1_000_000_000_000_000_000,
+            payload: Bytes::new(),
+            access_list: vec![(Address::repeat_byte(0x42), vec![H256::repeat_byte(1)])],
+            signature_y_parity: true,
+            signature_r: U256::from(1),
+            signature_s: U256::from(2),
+        });
+        // Typed transactions are wrapped in an RLP string holding their canonical encoding
+        let mut encoded = Vec::new();
+        tx.encode(&mut encoded);
+        let mut canonical = Vec::new();
+        tx.encode_canonical(&mut canonical);
+        assert_eq!(Bytes::decode(&encoded).unwrap(), canonical);
+        assert_eq!(Transaction::decode(&encoded).unwrap(), tx);
+        assert_eq!(Transaction::decode_canonical(&canonical).unwrap(), tx);
+    }
+
+    fn blob_transaction_network_form(
+        tx: &EIP4844Transaction,
+        blobs: usize,
+        blob_size: usize,
+    ) -> Vec<u8> {
+        let mut buf = vec![0x03];
+        Encoder::new(&mut buf)
+            .encode_field(tx)
+            .encode_field(&vec![Bytes::from(vec![0; blob_size]); blobs])
+            .encode_field(&vec![[0u8; 48]; blobs])
+            .encode_field(&vec![[0u8; 48]; blobs])
+            .finish();
+        buf
+    }
+
+    #[test]
+    fn decode_blob_transaction_network_form() {
+        let fixture = read_fixtures()
+            .transactions
+            .into_iter()
+            .find(|tx| tx.fixture.name == "sepolia EIP-4844 transaction")
+            .unwrap();
+        let rlp = hex::decode(fixture.fixture.rlp.trim_start_matches("0x")).unwrap();
+        let tx = Transaction::decode_canonical(&rlp).unwrap();
+        let Transaction::EIP4844Transaction(blob_tx) = &tx else {
+            panic!("Expected a blob transaction");
+        };
+        let blob_count = blob_tx.blob_versioned_hashes.len();
+
+        let network_form = blob_transaction_network_form(blob_tx, blob_count, BYTES_PER_BLOB);
+        let decoded = Transaction::decode_canonical(&network_form).unwrap();
+        assert_eq!(decoded, tx);
+        assert_eq!(decoded.compute_hash(), fixture.hash);
+
+        // The sidecar must hold one blob, commitment and proof per versioned hash
+        let missing_blob = blob_transaction_network_form(blob_tx, blob_count - 1, BYTES_PER_BLOB);
+        assert!(Transaction::decode_canonical(&missing_blob).is_err());
+        let short_blobs = blob_transaction_network_form(blob_tx, blob_count, 32);
+        assert!(Transaction::decode_canonical(&short_blobs).is_err());
+    }
+
+    mod proptests {
+        use proptest::{collection::vec, option, prelude::*};
+
+        use super::*;
+
+        fn h256() -> impl Strategy<Value = H256> {
+            any::<[u8; 32]>().prop_map(H256)
+        }
+
+        fn address() -> impl Strategy<Value = Address> {
+            any::<[u8; 20]>().prop_map(Address::from)
+        }
+
+        fn u256() -> impl Strategy<Value = U256> {
+            any::<[u64; 4]>().prop_map(U256)
+        }
+
+        fn bytes() -> impl Strategy<Value = Bytes> {
+            vec(any::<u8>(), 0..100).prop_map(Bytes::from)
+        }
+
+        fn bloom() -> impl Strategy<Value = Bloom> {
+            vec(any::<u8>(), 256).prop_map(|bloom| bloom.try_into().unwrap())
+        }
+
+        fn access_list() -> impl Strategy<Value = Vec<(Address, Vec<H256>)>> {
+            vec((address(), vec(h256(), 0..3)), 0..3)
+        }
+
+        fn legacy_transaction() -> impl Strategy<Value = LegacyTransaction> {
+            (
+                (u256(), any::<u64>(), any::<u64>(), address(), u256()),
+                (bytes(), u256(), u256(), u256()),
+            )
+                .prop_map(|((nonce, gas_price, gas, to, value), (data, v, r, s))| {
+                    LegacyTransaction {
+                        nonce,
+                        gas_price,
+                        gas,
+                        to,
+                        value,
+                        data,
+                        v,
+                        r,
+                        s,
+                    }
+                })
+        }
+
+        fn eip2930_transaction() -> impl Strategy<Value = EIP2930Transaction> {
+            (
+                (any::<u64>(), u256(), any::<u64>(), any::<u64>(), address()),
+                (u256(), bytes(), access_list()),
+                (any::<bool>(), u256(), u256()),
+            )
+                .prop_map(
+                    |(
+                        (chain_id, signer_nonce, gas_price, gas_limit, destination),
+                        (amount, payload, access_list),
+                        (signature_y_parity, signature_r, signature_s),
+                    )| EIP2930Transaction {
+                        chain_id,
+                        signer_nonce,
+                        gas_price,
+                        gas_limit,
+                        destination,
+                        amount,
+                        payload,
+                        access_list,
+                        signature_y_parity,
+                        signature_r,
+                        signature_s,
+                    },
+                )
+        }
+
+        fn eip1559_transaction() -> impl Strategy<Value = EIP1559Transaction> {
+            (
+                (
+                    any::<u64>(),
+                    u256(),
+                    any::<u64>(),
+                    any::<u64>(),
+                    any::<u64>(),
+                ),
+                (address(), any::<u64>(), bytes(), access_list()),
+                (any::<bool>(), u256(), u256()),
+            )
+                .prop_map(
+                    |(
+                        (
+                            chain_id,
+                            signer_nonce,
+                            max_priority_fee_per_gas,
+                            max_fee_per_gas,
+                            gas_limit,
+                        ),
+                        (destination, amount, payload, access_list),
+                        (signature_y_parity, signature_r, signature_s),
+                    )| EIP1559Transaction {
+                        chain_id,
+                        signer_nonce,
+                        max_priority_fee_per_gas,
+                        max_fee_per_gas,
+                        gas_limit,
+                        destination,
+                        amount,
+                        payload,
+                        access_list,
+                        signature_y_parity,
+                        signature_r,
+                        signature_s,
+                    },
+                )
+        }
+
+        fn eip4844_transaction() -> impl Strategy<Value = EIP4844Transaction> {
+            (
+                (
+                    any::<u64>(),
+                    u256(),
+                    any::<u64>(),
+                    any::<u64>(),
+                    any::<u64>(),
+                ),
+                (address(), u256(), bytes(), access_list()),
+                (u256(), vec(h256(), 0..6)),
+                (any::<bool>(), u256(), u256()),
+            )
+                .prop_map(
+                    |(
+                        (
+                            chain_id,
+                            signer_nonce,
+                            max_priority_fee_per_gas,
+                            max_fee_per_gas,
+                            gas_limit,
+                        ),
+                        (destination, amount, payload, access_list),
+                        (max_fee_per_blob_gas, blob_versioned_hashes),
+                        (signature_y_parity, signature_r, signature_s),
+                    )| EIP4844Transaction {
+                        chain_id,
+                        signer_nonce,
+                        max_priority_fee_per_gas,
+                        max_fee_per_gas,
+                        gas_limit,
+                        destination,
+                        amount,
+                        payload,
+                        access_list,
+                        max_fee_per_blob_gas,
+                        blob_versioned_hashes,
+                        signature_y_parity,
+                        signature_r,
+                        signature_s,
+                    },
+                )
+        }
+
+        fn transaction() -> impl Strategy<Value = Transaction> {
+            prop_oneof![
+                legacy_transaction().prop_map(Transaction::LegacyTransaction),
+                eip2930_transaction().prop_map(Transaction::EIP2930Transaction),
+                eip1559_transaction().prop_map(Transaction::EIP1559Transaction),
+                eip4844_transaction().prop_map(Transaction::EIP4844Transaction),
+            ]
+        }
+
+        fn block_header() -> impl Strategy<Value = BlockHeader> {
+            (
+                (h256(), h256(), address(), h256(), h256(), h256()),
+                (bloom(), u256(), any::<u64>(), any::<u64>(), any::<u64>()),
+                (any::<u64>(), bytes(), h256(), any::<u64>()),
+                (
+                    any::<u64>(),
+                    h256(),
+                    (any::<u64>(), any::<u64>()),
+                    h256(),
+                    // Number of post-London fields present in the header
+                    0..=5usize,
+                ),
+            )
+                .prop_map(
+                    |(
+                        (
+                            parent_hash,
+                            ommers_hash,
+                            coinbase,
+                            state_root,
+                            transactions_root,
+                            receipt_root,
+                        ),
+                        (logs_bloom, difficulty, number, gas_limit, gas_used),
+                        (timestamp, extra_data, prev_randao, nonce),
+                        (
+                            base_fee_per_gas,
+                            withdrawals_root,
+                            (blob_gas_used, excess_blob_gas),
+                            parent_beacon_block_root,
+                            fork_fields,
+                        ),
+                    )| BlockHeader {
+                        parent_hash,
+                        ommers_hash,
+                        coinbase,
+                        state_root,
+                        transactions_root,
+                        receipt_root,
+                        logs_bloom,
+                        difficulty,
+                        number,
+                        gas_limit,
+                        gas_used,
+                        timestamp,
+                        extra_data,
+                        prev_randao,
+                        nonce,
+                        base_fee_per_gas: (fork_fields > 0).then_some(base_fee_per_gas),
+                        withdrawals_root: (fork_fields > 1).then_some(withdrawals_root),
+                        blob_gas_used: (fork_fields > 2).then_some(blob_gas_used),
+                        excess_blob_gas: (fork_fields > 3).then_some(excess_blob_gas),
+                        parent_beacon_block_root: (fork_fields > 4)
+                            .then_some(parent_beacon_block_root),
+                    },
+                )
+        }
+
+        fn withdrawal() -> impl Strategy<Value = Withdrawal> {
+            (any::<u64>(), any::<u64>(), address(), u256()).prop_map(
+                |(index, validator_index, address, amount)| Withdrawal {
+                    index,
+                    validator_index,
+                    address,
+                    amount,
+                },
+            )
+        }
+
+        fn block() -> impl Strategy<Value = Block> {
+            (
+                block_header(),
+                vec(transaction(), 0..4),
+                vec(block_header(), 0..2),
+                option::of(vec(withdrawal(), 0..4)),
+            )
+                .prop_map(|(header, transactions, ommers, withdrawals)| Block {
+                    header,
+                    body: Body {
+                        transactions,
+                        ommers,
+                        withdrawals,
+                    },
+                })
+        }
+
+        fn assert_roundtrip<T: RLPEncode + RLPDecode + PartialEq + std::fmt::Debug>(value: &T) {
+            let mut encoded = Vec::new();
+            value.encode(&mut encoded);
+            assert_eq!(&T::decode(&encoded).unwrap(), value);
+        }
+
+        proptest! {
+            #[test]
+            fn transaction_roundtrip(tx in transaction()) {
+                assert_roundtrip(&tx);
+                let mut canonical = Vec::new();
+                tx.encode_canonical(&mut canonical);
+                prop_assert_eq!(Transaction::decode_canonical(&canonical).unwrap(), tx);
+            }
+
+            #[test]
+            fn block_header_roundtrip(header in block_header()) {
+                assert_roundtrip(&header);
+            }
+
+            #[test]
+            fn withdrawal_roundtrip(withdrawal in withdrawal()) {
+                assert_roundtrip(&withdrawal);
+            }
+
+            #[test]
+            fn block_roundtrip(block in block()) {
+                assert_roundtrip(&block);
+            }
+        }
     }
 }
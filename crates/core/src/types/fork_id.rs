@@ -0,0 +1,330 @@
+use crc32fast::Hasher;
+
+use crate::{
+    rlp::{
+        decode::RLPDecode,
+        encode::RLPEncode,
+        error::RLPDecodeError,
+        structs::{Decoder, Encoder},
+    },
+    H256, H32,
+};
+
+use super::{BlockNumber, ChainConfig};
+
+/// Fork activations above this value are timestamps rather than block numbers.
+/// It's the timestamp of the mainnet genesis block.
+const TIMESTAMP_THRESHOLD: u64 = 1438269973;
+
+/// Fork identifier as defined by EIP-2124, used to quickly tell whether
+/// a peer is on the same chain and fork schedule as us.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ForkId {
+    /// CRC32 checksum of the genesis hash and the activations of the forks passed so far.
+    pub fork_hash: H32,
+    /// Activation of the next known fork, or 0 if there is none.
+    pub fork_next: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum ForkIdError {
+    #[error("Remote node is stale and needs a software upgrade")]
+    RemoteStale,
+    #[error("Local node is on an incompatible chain or needs a software upgrade")]
+    LocalIncompatibleOrStale,
+}
+
+/// Chain information needed to compute and validate fork ids.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForkFilter {
+    /// Fork activations, block based ones first, each list sorted and without repetitions.
+    forks: Vec<u64>,
+    /// Checksum for each fork stage: `sums[0]` is the genesis one and
+    /// `sums[i]` the one once `forks[i - 1]` is active.
+    sums: Vec<H32>,
+}
+
+impl ForkFilter {
+    pub fn new(config: &ChainConfig, genesis_hash: H256, genesis_timestamp: u64) -> Self {
+        let (blocks, times) = config.gather_forks(genesis_timestamp);
+        let forks: Vec<u64> = blocks.into_iter().chain(times).collect();
+
+        let mut hasher = Hasher::new();
+        hasher.update(genesis_hash.as_bytes());
+        let mut sums = vec![checksum(&hasher)];
+        for fork in &forks {
+            hasher.update(&fork.to_be_bytes());
+            sums.push(checksum(&hasher));
+        }
+        Self { forks, sums }
+    }
+
+    /// Returns the fork id of the chain at the given head.
+    pub fn fork_id(&self, head_number: BlockNumber, head_timestamp: u64) -> ForkId {
+        let stage = self.stage(head_number, head_timestamp);
+        ForkId {
+            fork_hash: self.sums[stage],
+            fork_next: self.forks.get(stage).copied().unwrap_or(0),
+        }
+    }
+
+    /// Validates a remote fork id against our chain at the given head,
+    /// following the rules from EIP-2124.
+    pub fn validate(
+        &self,
+        remote: ForkId,
+        head_number: BlockNumber,
+        head_timestamp: u64,
+    ) -> Result<(), ForkIdError> {
+        let stage = self.stage(head_number, head_timestamp);
+        let is_passed = |activation: u64| {
+            if activation > TIMESTAMP_THRESHOLD {
+                head_timestamp >= activation
+            } else {
+                head_number >= activation
+            }
+        };
+
+        // Both are at the same fork stage. Reject if we already passed the remote's
+        // announced next fork, as it doesn't know about a fork we did.
+        if remote.fork_hash == self.sums[stage] {
+            if remote.fork_next != 0 && is_passed(remote.fork_next) {
+                return Err(ForkIdError::LocalIncompatibleOrStale);
+            }
+            return Ok(());
+        }
+        // The remote is on one of our past fork stages. Its next fork must be the one we
+        // activated right after that stage, otherwise it's missing a fork we know of.
+        if let Some(remote_stage) = self.sums[..stage]
+            .iter()
+            .position(|sum| *sum == remote.fork_hash)
+        {
+            if remote.fork_next != self.forks[remote_stage] {
+                return Err(ForkIdError::RemoteStale);
+            }
+            return Ok(());
+        }
+        // The remote is on one of our future fork stages, we still have to sync to it.
+        if self.sums[stage + 1..].contains(&remote.fork_hash) {
+            return Ok(());
+        }
+        Err(ForkIdError::LocalIncompatibleOrStale)
+    }
+
+    /// Returns the amount of forks active at the given head.
+    fn stage(&self, head_number: BlockNumber, head_timestamp: u64) -> usize {
+        self.forks
+            .iter()
+            .take_while(|activation| {
+                if **activation > TIMESTAMP_THRESHOLD {
+                    head_timestamp >= **activation
+                } else {
+                    head_number >= **activation
+                }
+            })
+            .count()
+    }
+}
+
+fn checksum(hasher: &Hasher) -> H32 {
+    H32(hasher.clone().finalize().to_be_bytes())
+}
+
+impl ChainConfig {
+    /// Returns the block numbers and timestamps at which forks activate after genesis,
+    /// each sorted and without repetitions, as needed for EIP-2124 fork ids.
+    pub fn gather_forks(&self, genesis_timestamp: u64) -> (Vec<u64>, Vec<u64>) {
+        let mut blocks: Vec<u64> = [
+            self.homestead_block,
+            self.dao_fork_block,
+            self.eip150_block,
+            self.eip155_block,
+            self.eip158_block,
+            self.byzantium_block,
+            self.constantinople_block,
+            self.petersburg_block,
+            self.istanbul_block,
+            self.muir_glacier_block,
+            self.berlin_block,
+            self.london_block,
+            self.arrow_glacier_block,
+            self.gray_glacier_block,
+            self.merge_netsplit_block,
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|block| *block != 0)
+        .collect();
+        blocks.sort_unstable();
+        blocks.dedup();
+
+        let mut times: Vec<u64> = [
+            self.shanghai_time,
+            self.cancun_time,
+            self.prague_time,
+            self.verkle_time,
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|time| *time > genesis_timestamp)
+        .collect();
+        times.sort_unstable();
+        times.dedup();
+
+        (blocks, times)
+    }
+}
+
+impl RLPEncode for ForkId {
+    fn encode(&self, buf: &mut dyn bytes::BufMut) {
+        Encoder::new(buf)
+            .encode_field(&self.fork_hash)
+            .encode_field(&self.fork_next)
+            .finish();
+    }
+}
+
+impl RLPDecode for ForkId {
+    fn decode_unfinished(rlp: &[u8]) -> Result<(Self, &[u8]), RLPDecodeError> {
+        let decoder = Decoder::new(rlp)?;
+        let (fork_hash, decoder) = decoder.decode_field("fork_hash")?;
+        let (fork_next, decoder) = decoder.decode_field("fork_next")?;
+        let fork_id = ForkId {
+            fork_hash,
+            fork_next,
+        };
+        Ok((fork_id, decoder.finish()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::U256;
+
+    use super::*;
+
+    fn mainnet_filter() -> ForkFilter {
+        let config = ChainConfig {
+            chain_id: U256::from(1),
+            homestead_block: Some(1_150_000),
+            dao_fork_block: Some(1_920_000),
+            dao_fork_support: true,
+            eip150_block: Some(2_463_000),
+            eip155_block: Some(2_675_000),
+            eip158_block: Some(2_675_000),
+            byzantium_block: Some(4_370_000),
+            constantinople_block: Some(7_280_000),
+            petersburg_block: Some(7_280_000),
+            istanbul_block: Some(9_069_000),
+            muir_glacier_block: Some(9_200_000),
+            berlin_block: Some(12_244_000),
+            london_block: Some(12_965_000),
+            arrow_glacier_block: Some(13_773_000),
+            gray_glacier_block: Some(15_050_000),
+            shanghai_time: Some(1_681_338_455),
+            cancun_time: Some(1_710_338_135),
+            ..Default::default()
+        };
+        let genesis_hash =
+            H256::from_str("d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3")
+                .unwrap();
+        ForkFilter::new(&config, genesis_hash, 0)
+    }
+
+    fn fork_id(fork_hash: u32, fork_next: u64) -> ForkId {
+        ForkId {
+            fork_hash: H32(fork_hash.to_be_bytes()),
+            fork_next,
+        }
+    }
+
+    #[test]
+    fn mainnet_fork_ids() {
+        let filter = mainnet_filter();
+        let cases = [
+            (0, 0, fork_id(0xfc64ec04, 1_150_000)),
+            (1_149_999, 0, fork_id(0xfc64ec04, 1_150_000)),
+            (1_150_000, 0, fork_id(0x97c2c34c, 1_920_000)),
+            (1_920_000, 0, fork_id(0x91d1f948, 2_463_000)),
+            (2_463_000, 0, fork_id(0x7a64da13, 2_675_000)),
+            (2_675_000, 0, fork_id(0x3edd5b10, 4_370_000)),
+            (4_370_000, 0, fork_id(0xa00bc324, 7_280_000)),
+            (7_280_000, 0, fork_id(0x668db0af, 9_069_000)),
+            (9_069_000, 0, fork_id(0x879d6e30, 9_200_000)),
+            (9_200_000, 0, fork_id(0xe029e991, 12_244_000)),
+            (12_244_000, 0, fork_id(0x0eb440f6, 12_965_000)),
+            (12_965_000, 0, fork_id(0xb715077d, 13_773_000)),
+            (13_773_000, 0, fork_id(0x20c327fc, 15_050_000)),
+            (15_050_000, 0, fork_id(0xf0afd0e3, 1_681_338_455)),
+            (
+                20_000_000,
+                1_681_338_455,
+                fork_id(0xdce96c2d, 1_710_338_135),
+            ),
+            (20_000_000, 1_710_338_135, fork_id(0x9f3d2254, 0)),
+        ];
+        for (number, timestamp, expected) in cases {
+            assert_eq!(filter.fork_id(number, timestamp), expected, "{number}");
+        }
+    }
+
+    #[test]
+    fn validate_fork_ids() {
+        let filter = mainnet_filter();
+        let petersburg_head = 7_987_396;
+        // Same fork, no next fork announced
+        assert_eq!(
+            filter.validate(fork_id(0x668db0af, 0), petersburg_head, 0),
+            Ok(())
+        );
+        // Same fork, remote announces a fork we don't know of yet
+        assert_eq!(
+            filter.validate(fork_id(0x668db0af, u64::MAX), petersburg_head, 0),
+            Ok(())
+        );
+        // Same fork, but we already passed the remote's next fork without forking
+        assert_eq!(
+            filter.validate(fork_id(0x668db0af, petersburg_head), petersburg_head, 0),
+            Err(ForkIdError::LocalIncompatibleOrStale)
+        );
+        // Remote is syncing on a past fork and knows about the next one
+        assert_eq!(
+            filter.validate(fork_id(0xa00bc324, 7_280_000), petersburg_head, 0),
+            Ok(())
+        );
+        // Remote is on a past fork and doesn't know about the next one
+        assert_eq!(
+            filter.validate(fork_id(0xa00bc324, 0), petersburg_head, 0),
+            Err(ForkIdError::RemoteStale)
+        );
+        // Remote is on a future fork we know of
+        assert_eq!(
+            filter.validate(fork_id(0x668db0af, 0), 7_279_999, 0),
+            Ok(())
+        );
+        // Remote is on an unknown fork
+        assert_eq!(
+            filter.validate(fork_id(0x5cddc0e1, 0), petersburg_head, 0),
+            Err(ForkIdError::LocalIncompatibleOrStale)
+        );
+        // Timestamp based forks are checked against the head timestamp
+        assert_eq!(
+            filter.validate(
+                fork_id(0xdce96c2d, 1_710_338_135),
+                20_000_000,
+                1_710_338_135
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn fork_id_rlp_roundtrip() {
+        let id = fork_id(0x9f3d2254, 1_710_338_135);
+        let mut buf = Vec::new();
+        id.encode(&mut buf);
+        assert_eq!(ForkId::decode(&buf).unwrap(), id);
+    }
+}
@@ -75,6 +75,89 @@ pub struct ChainConfig {
     pub terminal_total_difficulty_passed: bool,
 }
 
+/// Network upgrades, in activation order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Fork {
+    Frontier,
+    Homestead,
+    DaoFork,
+    Tangerine,
+    SpuriousDragon,
+    Byzantium,
+    Constantinople,
+    Petersburg,
+    Istanbul,
+    MuirGlacier,
+    Berlin,
+    London,
+    ArrowGlacier,
+    GrayGlacier,
+    Paris,
+    Shanghai,
+    Cancun,
+    Prague,
+}
+
+impl ChainConfig {
+    /// Returns the latest fork active at the given block.
+    /// Forks up to the merge activate by block number, later ones by timestamp.
+    pub fn fork(&self, block_number: u64, timestamp: u64) -> Fork {
+        let by_time = |time: Option<u64>| time.is_some_and(|time| timestamp >= time);
+        if by_time(self.prague_time) {
+            return Fork::Prague;
+        }
+        if by_time(self.cancun_time) {
+            return Fork::Cancun;
+        }
+        if by_time(self.shanghai_time) {
+            return Fork::Shanghai;
+        }
+        if self.is_paris_activated(block_number) {
+            return Fork::Paris;
+        }
+        let by_block = |block: Option<u64>| block.is_some_and(|block| block_number >= block);
+        [
+            (self.gray_glacier_block, Fork::GrayGlacier),
+            (self.arrow_glacier_block, Fork::ArrowGlacier),
+            (self.london_block, Fork::London),
+            (self.berlin_block, Fork::Berlin),
+            (self.muir_glacier_block, Fork::MuirGlacier),
+            (self.istanbul_block, Fork::Istanbul),
+            (self.petersburg_block, Fork::Petersburg),
+            (self.constantinople_block, Fork::Constantinople),
+            (self.byzantium_block, Fork::Byzantium),
+            (self.eip158_block, Fork::SpuriousDragon),
+            (self.eip150_block, Fork::Tangerine),
+            (self.dao_fork_block, Fork::DaoFork),
+            (self.homestead_block, Fork::Homestead),
+        ]
+        .into_iter()
+        .find(|(block, _)| by_block(*block))
+        .map_or(Fork::Frontier, |(_, fork)| fork)
+    }
+
+    /// Returns the latest fork active at the chain's head.
+    /// Unlike [`ChainConfig::fork`], this trusts `terminal_total_difficulty_passed`,
+    /// which only tells that the network is past the merge by now.
+    pub fn head_fork(&self, block_number: u64, timestamp: u64) -> Fork {
+        let fork = self.fork(block_number, timestamp);
+        if fork < Fork::Paris && self.terminal_total_difficulty_passed {
+            return Fork::Paris;
+        }
+        fork
+    }
+
+    /// The merge is triggered by total difficulty, which isn't known from the block alone.
+    /// It is considered active from the merge netsplit block on, or from genesis
+    /// on networks with a terminal total difficulty of zero.
+    fn is_paris_activated(&self, block_number: u64) -> bool {
+        self.terminal_total_difficulty == Some(U256::zero())
+            || self
+                .merge_netsplit_block
+                .is_some_and(|block| block_number >= block)
+    }
+}
+
 #[allow(unused)]
 #[derive(Debug, Deserialize, PartialEq)]
 pub struct GenesisAccount {
@@ -177,4 +260,77 @@ mod tests {
             )
         );
     }
+
+    fn mainnet_config() -> ChainConfig {
+        ChainConfig {
+            chain_id: U256::from(1),
+            homestead_block: Some(1_150_000),
+            dao_fork_block: Some(1_920_000),
+            dao_fork_support: true,
+            eip150_block: Some(2_463_000),
+            eip155_block: Some(2_675_000),
+            eip158_block: Some(2_675_000),
+            byzantium_block: Some(4_370_000),
+            constantinople_block: Some(7_280_000),
+            petersburg_block: Some(7_280_000),
+            istanbul_block: Some(9_069_000),
+            muir_glacier_block: Some(9_200_000),
+            berlin_block: Some(12_244_000),
+            london_block: Some(12_965_000),
+            arrow_glacier_block: Some(13_773_000),
+            gray_glacier_block: Some(15_050_000),
+            merge_netsplit_block: Some(15_537_394),
+            shanghai_time: Some(1_681_338_455),
+            cancun_time: Some(1_710_338_135),
+            terminal_total_difficulty: Some(U256::from_dec_str("58750000000000000000000").unwrap()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn fork_activation() {
+        let config = mainnet_config();
+        assert_eq!(config.fork(0, 0), Fork::Frontier);
+        assert_eq!(config.fork(1_150_000, 0), Fork::Homestead);
+        assert_eq!(config.fork(2_675_000, 0), Fork::SpuriousDragon);
+        // Constantinople and Petersburg activated on the same block
+        assert_eq!(config.fork(7_280_000, 0), Fork::Petersburg);
+        assert_eq!(config.fork(12_965_000, 0), Fork::London);
+        assert_eq!(config.fork(15_537_394, 1_663_224_179), Fork::Paris);
+        assert_eq!(config.fork(17_034_870, 1_681_338_455), Fork::Shanghai);
+        assert_eq!(config.fork(19_426_587, 1_710_338_135), Fork::Cancun);
+    }
+
+    #[test]
+    fn terminal_total_difficulty_passed_only_affects_the_head() {
+        let config = ChainConfig {
+            terminal_total_difficulty_passed: true,
+            ..mainnet_config()
+        };
+        assert_eq!(config.fork(1_150_000, 0), Fork::Homestead);
+        assert_eq!(config.fork(15_050_000, 0), Fork::GrayGlacier);
+        assert_eq!(config.fork(15_537_394, 1_663_224_179), Fork::Paris);
+        assert_eq!(config.head_fork(15_050_000, 0), Fork::Paris);
+        assert_eq!(config.head_fork(17_034_870, 1_681_338_455), Fork::Shanghai);
+    }
+
+    #[test]
+    fn merge_at_genesis() {
+        let config = ChainConfig {
+            london_block: Some(0),
+            terminal_total_difficulty: Some(U256::zero()),
+            ..Default::default()
+        };
+        assert_eq!(config.fork(0, 0), Fork::Paris);
+        assert_eq!(config.fork(100, 0), Fork::Paris);
+    }
+
+    #[test]
+    fn fork_activation_of_genesis_file() {
+        let file = File::open("../../test_data/genesis.json").expect("Failed to open genesis file");
+        let genesis: Genesis = serde_json::from_reader(BufReader::new(file))
+            .expect("Failed to deserialize genesis file");
+        assert_eq!(genesis.config.fork(0, genesis.timestamp), Fork::Cancun);
+        assert_eq!(genesis.config.fork(0, 1718232101), Fork::Prague);
+    }
 }
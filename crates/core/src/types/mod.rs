@@ -1,9 +1,11 @@
 mod account;
 mod block;
+mod fork_id;
 mod genesis;
 mod receipt;
 
 pub use account::*;
 pub use block::*;
+pub use fork_id::*;
 pub use genesis::*;
 pub use receipt::*;
@@ -1,4 +1,10 @@
-use crate::rlp::encode::RLPEncode;
+use crate::rlp::{
+    constants::RLP_EMPTY_LIST,
+    decode::RLPDecode,
+    encode::RLPEncode,
+    error::RLPDecodeError,
+    structs::{Decoder, Encoder},
+};
 use crate::types::Bloom;
 use bytes::Bytes;
 use ethereum_types::{Address, H256};
@@ -7,18 +13,87 @@ pub type Index = u64;
 /// Result of a transaction
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Receipt {
+    /// Type of the transaction that produced the receipt (EIP-2718).
+    tx_type: u8,
     succeeded: bool,
     cumulative_gas_used: u64,
     bloom: Bloom,
     logs: Vec<Log>,
 }
 
+impl Receipt {
+    /// Encodes the receipt in its canonical form, as stored in the receipts trie:
+    /// legacy receipts as an RLP list, and typed receipts as the transaction type
+    /// followed by their RLP payload.
+    pub fn encode_canonical(&self, buf: &mut dyn bytes::BufMut) {
+        if self.tx_type != 0x00 {
+            buf.put_u8(self.tx_type);
+        }
+        Encoder::new(buf)
+            .encode_field(&self.succeeded)
+            .encode_field(&self.cumulative_gas_used)
+            .encode_field(&self.bloom)
+            .encode_field(&self.logs)
+            .finish();
+    }
+
+    /// Decodes a receipt from its canonical form. See [`Receipt::encode_canonical`].
+    pub fn decode_canonical(bytes: &[u8]) -> Result<Self, RLPDecodeError> {
+        let (tx_type, payload) = bytes.split_first().ok_or(RLPDecodeError::InvalidLength)?;
+        if *tx_type >= RLP_EMPTY_LIST {
+            return Self::decode(bytes);
+        }
+        if !(0x01..=0x03).contains(tx_type) {
+            return Err(RLPDecodeError::Custom(format!(
+                "Unsupported receipt type: {tx_type}"
+            )));
+        }
+        let (receipt, rest) = Self::decode_payload(*tx_type, payload)?;
+        if !rest.is_empty() {
+            return Err(RLPDecodeError::InvalidLength);
+        }
+        Ok(receipt)
+    }
+
+    fn decode_payload(tx_type: u8, rlp: &[u8]) -> Result<(Self, &[u8]), RLPDecodeError> {
+        let decoder = Decoder::new(rlp)?;
+        let (succeeded, decoder) = decoder.decode_field("succeeded")?;
+        let (cumulative_gas_used, decoder) = decoder.decode_field("cumulative_gas_used")?;
+        let (bloom, decoder) = decoder.decode_field("bloom")?;
+        let (logs, decoder) = decoder.decode_field("logs")?;
+        let receipt = Receipt {
+            tx_type,
+            succeeded,
+            cumulative_gas_used,
+            bloom,
+            logs,
+        };
+        Ok((receipt, decoder.finish()?))
+    }
+}
+
+/// Receipts are encoded like transactions in a block body: legacy receipts as
+/// an RLP list, and typed receipts as an RLP string containing their canonical
+/// encoding.
 impl RLPEncode for Receipt {
     fn encode(&self, buf: &mut dyn bytes::BufMut) {
-        self.succeeded.encode(buf);
-        self.cumulative_gas_used.encode(buf);
-        self.bloom.encode(buf);
-        self.logs.encode(buf);
+        if self.tx_type == 0x00 {
+            return self.encode_canonical(buf);
+        }
+        let mut typed_receipt = Vec::new();
+        self.encode_canonical(&mut typed_receipt);
+        typed_receipt.as_slice().encode(buf)
+    }
+}
+
+impl RLPDecode for Receipt {
+    fn decode_unfinished(rlp: &[u8]) -> Result<(Self, &[u8]), RLPDecodeError> {
+        let first_byte = *rlp.first().ok_or(RLPDecodeError::InvalidLength)?;
+        if first_byte >= RLP_EMPTY_LIST {
+            return Receipt::decode_payload(0x00, rlp);
+        }
+        let (typed_receipt, rest) = Bytes::decode_unfinished(rlp)?;
+        Ok((Receipt::decode_canonical(&typed_receipt)?, rest))
     }
 }
 
@@ -32,8 +107,77 @@ pub struct Log {
 
 impl RLPEncode for Log {
     fn encode(&self, buf: &mut dyn bytes::BufMut) {
-        self.address.encode(buf);
-        self.topics.encode(buf);
-        self.data.encode(buf);
+        Encoder::new(buf)
+            .encode_field(&self.address)
+            .encode_field(&self.topics)
+            .encode_field(&self.data)
+            .finish();
+    }
+}
+
+impl RLPDecode for Log {
+    fn decode_unfinished(rlp: &[u8]) -> Result<(Self, &[u8]), RLPDecodeError> {
+        let decoder = Decoder::new(rlp)?;
+        let (address, decoder) = decoder.decode_field("address")?;
+        let (topics, decoder) = decoder.decode_field("topics")?;
+        let (data, decoder) = decoder.decode_field("data")?;
+        let log = Log {
+            address,
+            topics,
+            data,
+        };
+        Ok((log, decoder.finish()?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::{collection::vec, prelude::*};
+
+    use super::*;
+
+    fn log() -> impl Strategy<Value = Log> {
+        (
+            any::<[u8; 20]>(),
+            vec(any::<[u8; 32]>(), 0..5),
+            vec(any::<u8>(), 0..100),
+        )
+            .prop_map(|(address, topics, data)| Log {
+                address: Address::from(address),
+                topics: topics.into_iter().map(H256).collect(),
+                data: Bytes::from(data),
+            })
+    }
+
+    fn receipt() -> impl Strategy<Value = Receipt> {
+        (
+            0..=3u8,
+            any::<bool>(),
+            any::<u64>(),
+            vec(any::<u8>(), 256),
+            vec(log(), 0..4),
+        )
+            .prop_map(
+                |(tx_type, succeeded, cumulative_gas_used, bloom, logs)| Receipt {
+                    tx_type,
+                    succeeded,
+                    cumulative_gas_used,
+                    bloom: bloom.try_into().unwrap(),
+                    logs,
+                },
+            )
+    }
+
+    proptest! {
+        #[test]
+        fn receipt_roundtrip(receipt in receipt()) {
+            let mut encoded = Vec::new();
+            receipt.encode(&mut encoded);
+            prop_assert_eq!(&Receipt::decode(&encoded).unwrap(), &receipt);
+
+            let mut canonical = Vec::new();
+            receipt.encode_canonical(&mut canonical);
+            prop_assert_eq!(Receipt::decode_canonical(&canonical).unwrap(), receipt);
+        }
     }
 }
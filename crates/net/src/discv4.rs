@@ -1,7 +1,10 @@
 use std::net::IpAddr;
 
 use bytes::BufMut;
-use ethrex_core::rlp::{encode::RLPEncode, structs};
+use ethrex_core::{
+    hash::keccak,
+    rlp::{encode::RLPEncode, structs},
+};
 use k256::ecdsa::{signature::Signer, SigningKey};
 
 #[derive(Debug)]
@@ -20,7 +23,7 @@ pub(crate) enum Message {
 }
 
 impl Message {
-    pub fn encode_with_header(&self, buf: &mut dyn BufMut, node_signer: SigningKey) {
+    pub fn encode_with_header(&self, buf: &mut dyn BufMut, node_signer: &SigningKey) {
         let signature_size = 65_usize;
         let mut data: Vec<u8> = Vec::with_capacity(signature_size.next_power_of_two());
         data.resize(signature_size, 0);
@@ -30,7 +33,7 @@ impl Message {
             _ => todo!(),
         }
 
-        let digest = keccak_hash::keccak_buffer(&mut &data[signature_size..]).unwrap();
+        let digest = keccak(&data[signature_size..]);
 
         let (signature, recovery_id) = node_signer.try_sign(&digest.0).expect("failed to sign");
         let b = signature.to_bytes();
@@ -38,7 +41,7 @@ impl Message {
         data[..signature_size - 1].copy_from_slice(&b);
         data[signature_size - 1] = recovery_id.to_byte();
 
-        let hash = keccak_hash::keccak_buffer(&mut &data[..]).unwrap();
+        let hash = keccak(&data[..]);
         buf.put_slice(&hash.0);
         buf.put_slice(&data[..]);
     }
@@ -98,8 +101,6 @@ impl PingMessage {
         }
     }
 
-    // TODO: remove when used
-    #[allow(unused)]
     pub fn with_enr_seq(self, enr_seq: u64) -> Self {
         Self {
             enr_seq: Some(enr_seq),
@@ -125,7 +126,7 @@ mod tests {
     use std::{fmt::Write, str::FromStr};
 
     use super::*;
-    use keccak_hash::H256;
+    use ethrex_core::H256;
 
     fn to_hex(bytes: &[u8]) -> String {
         bytes.iter().fold(String::new(), |mut buf, b| {
@@ -158,7 +159,7 @@ mod tests {
 
         let mut buf = Vec::new();
 
-        msg.encode_with_header(&mut buf, signer);
+        msg.encode_with_header(&mut buf, &signer);
         let result = to_hex(&buf);
         let hash = "d9b83d9701c6481a99db908b19551c6b082bcb28d5bef44cfa55256bc7977500";
         let signature = "f0bff907b5c432e623ba5d3803d6a405bdbaffdfc0373499ac2a243ef3ab52de3a5312c0a9a96593979b746a4cd37ebdf21cf6971cf8c10c94f4d45c1a0f90dd00";
@@ -0,0 +1,366 @@
+//! Client for node lists published as DNS trees (EIP-1459).
+//!
+//! A tree is a set of TXT records under a domain: a signed root pointing to the root
+//! hashes of two subtrees, one with node records and one with links to other trees.
+//! Subtree entries live at `<hash>.<domain>`, where the hash is taken from their content.
+
+use std::{collections::HashSet, future::Future, str::FromStr};
+
+use base32::Alphabet;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use ethrex_core::hash::keccak;
+use hickory_resolver::{error::ResolveError, TokioAsyncResolver};
+use k256::ecdsa::{signature::hazmat::PrehashVerifier, Signature, VerifyingKey};
+
+use crate::types::NodeRecord;
+
+/// Maximum amount of entries fetched from a single tree, to bound the work done on
+/// malicious or misconfigured trees.
+const MAX_TREE_ENTRIES: usize = 10_000;
+
+const BASE32: Alphabet = Alphabet::RFC4648 { padding: false };
+
+/// Source of the TXT records a tree is published in.
+pub trait TxtResolver {
+    /// Returns the content of the TXT record at the given name, with its strings concatenated.
+    fn lookup_txt(&self, name: &str) -> impl Future<Output = Option<String>> + Send;
+}
+
+/// Resolver that looks TXT records up through the system's DNS configuration.
+pub struct SystemTxtResolver(TokioAsyncResolver);
+
+impl SystemTxtResolver {
+    pub fn new() -> Result<Self, ResolveError> {
+        TokioAsyncResolver::tokio_from_system_conf().map(SystemTxtResolver)
+    }
+}
+
+impl TxtResolver for SystemTxtResolver {
+    async fn lookup_txt(&self, name: &str) -> Option<String> {
+        // Names are fully qualified, so the system's search domains aren't tried
+        let lookup = self.0.txt_lookup(format!("{name}.")).await.ok()?;
+        // Entries longer than 255 bytes are split into several strings of a single record
+        let txt = lookup.iter().next()?;
+        let content = txt
+            .txt_data()
+            .iter()
+            .flat_map(|s| s.iter().copied())
+            .collect();
+        String::from_utf8(content).ok()
+    }
+}
+
+/// Location of a tree and the key its root must be signed with, in the
+/// `enrtree://<base32 public key>@<domain>` format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeLink {
+    pub public_key: VerifyingKey,
+    pub domain: String,
+}
+
+/// Node records and links to other trees found in a tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tree {
+    pub seq: u64,
+    pub records: Vec<NodeRecord>,
+    pub links: Vec<TreeLink>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum DnsDiscoveryError {
+    #[error("Invalid tree link, expected enrtree://<public key>@<domain>")]
+    InvalidLink,
+    /// The record at the given name is missing or malformed.
+    #[error("Missing or malformed tree entry at {0}")]
+    InvalidEntry(String),
+    /// The record at the given name doesn't match the hash it was looked up by.
+    #[error("Tree entry at {0} doesn't match its hash")]
+    HashMismatch(String),
+    #[error("Invalid tree root signature")]
+    InvalidSignature,
+    #[error("Tree has more than {MAX_TREE_ENTRIES} entries")]
+    TooManyEntries,
+}
+
+/// Subtree an entry was found in, which determines the kind of leaves it may have.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Subtree {
+    Records,
+    Links,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Entry {
+    Root {
+        enr_root: String,
+        link_root: String,
+        seq: u64,
+        signature: Vec<u8>,
+    },
+    Branch(Vec<String>),
+    Record(Box<NodeRecord>),
+    Link(TreeLink),
+}
+
+impl FromStr for TreeLink {
+    type Err = DnsDiscoveryError;
+
+    fn from_str(input: &str) -> Result<TreeLink, DnsDiscoveryError> {
+        let (public_key, domain) = input
+            .strip_prefix("enrtree://")
+            .and_then(|link| link.split_once('@'))
+            .ok_or(DnsDiscoveryError::InvalidLink)?;
+        let public_key = base32::decode(BASE32, public_key)
+            .and_then(|key| VerifyingKey::from_sec1_bytes(&key).ok())
+            .ok_or(DnsDiscoveryError::InvalidLink)?;
+        if domain.is_empty() {
+            return Err(DnsDiscoveryError::InvalidLink);
+        }
+        Ok(TreeLink {
+            public_key,
+            domain: domain.to_string(),
+        })
+    }
+}
+
+impl Entry {
+    fn parse(txt: &str) -> Option<Entry> {
+        if let Some(root) = txt.strip_prefix("enrtree-root:v1 ") {
+            let mut fields = root.split(' ');
+            let mut field = |name: &str| fields.next()?.strip_prefix(name);
+            let enr_root = field("e=")?.to_string();
+            let link_root = field("l=")?.to_string();
+            let seq = field("seq=")?.parse().ok()?;
+            let signature = URL_SAFE_NO_PAD.decode(field("sig=")?).ok()?;
+            Some(Entry::Root {
+                enr_root,
+                link_root,
+                seq,
+                signature,
+            })
+        } else if let Some(branch) = txt.strip_prefix("enrtree-branch:") {
+            let hashes = branch
+                .split(',')
+                .filter(|hash| !hash.is_empty())
+                .map(str::to_string)
+                .collect();
+            Some(Entry::Branch(hashes))
+        } else if txt.starts_with("enrtree://") {
+            TreeLink::from_str(txt).ok().map(Entry::Link)
+        } else if txt.starts_with("enr:") {
+            NodeRecord::from_str(txt)
+                .ok()
+                .map(|record| Entry::Record(Box::new(record)))
+        } else {
+            None
+        }
+    }
+}
+
+/// Fetches the whole tree at the given link, checking the root signature
+/// and the hash of every entry.
+pub async fn resolve_tree<R: TxtResolver>(
+    resolver: &R,
+    link: &TreeLink,
+) -> Result<Tree, DnsDiscoveryError> {
+    let root_txt = resolver
+        .lookup_txt(&link.domain)
+        .await
+        .ok_or_else(|| DnsDiscoveryError::InvalidEntry(link.domain.clone()))?;
+    let Some(Entry::Root {
+        enr_root,
+        link_root,
+        seq,
+        signature,
+    }) = Entry::parse(&root_txt)
+    else {
+        return Err(DnsDiscoveryError::InvalidEntry(link.domain.clone()));
+    };
+    // The signature covers the root record up to the signature field, and has
+    // the recovery id appended, which isn't needed to verify it
+    let signed_content = format!("enrtree-root:v1 e={enr_root} l={link_root} seq={seq}");
+    if signature.len() != 65 {
+        return Err(DnsDiscoveryError::InvalidSignature);
+    }
+    let signature =
+        Signature::from_slice(&signature[..64]).map_err(|_| DnsDiscoveryError::InvalidSignature)?;
+    link.public_key
+        .verify_prehash(&keccak(signed_content).0, &signature)
+        .map_err(|_| DnsDiscoveryError::InvalidSignature)?;
+
+    let mut tree = Tree {
+        seq,
+        records: Vec::new(),
+        links: Vec::new(),
+    };
+    let mut visited = HashSet::new();
+    let mut pending = vec![(enr_root, Subtree::Records), (link_root, Subtree::Links)];
+    while let Some((hash, subtree)) = pending.pop() {
+        if !visited.insert(hash.clone()) {
+            continue;
+        }
+        if visited.len() > MAX_TREE_ENTRIES {
+            return Err(DnsDiscoveryError::TooManyEntries);
+        }
+        let name = format!("{hash}.{}", link.domain);
+        let txt = resolver
+            .lookup_txt(&name)
+            .await
+            .ok_or_else(|| DnsDiscoveryError::InvalidEntry(name.clone()))?;
+        if !hash.eq_ignore_ascii_case(&entry_hash(&txt)) {
+            return Err(DnsDiscoveryError::HashMismatch(name));
+        }
+        match (Entry::parse(&txt), subtree) {
+            (Some(Entry::Branch(hashes)), _) => {
+                pending.extend(hashes.into_iter().map(|hash| (hash, subtree)))
+            }
+            (Some(Entry::Record(record)), Subtree::Records) => tree.records.push(*record),
+            (Some(Entry::Link(link)), Subtree::Links) => tree.links.push(link),
+            _ => return Err(DnsDiscoveryError::InvalidEntry(name)),
+        }
+    }
+    Ok(tree)
+}
+
+/// Returns the subdomain an entry is published at: the base32 encoding
+/// of the first 16 bytes of the hash of its content.
+fn entry_hash(txt: &str) -> String {
+    base32::encode(BASE32, &keccak(txt).0[..16])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use k256::{ecdsa::SigningKey, elliptic_curve::rand_core::OsRng};
+
+    use super::*;
+
+    // Example tree from EIP-1459
+    const TREE_LINK: &str =
+        "enrtree://AKPYQIUQIL7PSIACI32J7FGZW56E5FKHEFCCOFHILBIMW3M6LWXS2@nodes.example.org";
+    const TREE: [(&str, &str); 6] = [
+        ("nodes.example.org", "enrtree-root:v1 e=JWXYDBPXYWG6FX3GMDIBFA6CJ4 l=C7HRFPF3BLGF3YR4DY5KX3SMBE seq=1 sig=o908WmNp7LibOfPsr4btQwatZJ5URBr2ZAuxvK4UWHlsB9sUOTJQaGAlLPVAhM__XJesCHxLISo94z5Z2a463gA"),
+        ("C7HRFPF3BLGF3YR4DY5KX3SMBE.nodes.example.org", "enrtree://AM5FCQLWIZX2QFPNJAP7VUERCCRNGRHWZG3YYHIUV7BVDQ5FDPRT2@morenodes.example.org"),
+        ("JWXYDBPXYWG6FX3GMDIBFA6CJ4.nodes.example.org", "enrtree-branch:2XS2367YHAXJFGLZHVAWLQD4ZY,H4FHT4B454P6UXFD7JCYQ5PWDY,MHTDO6TMUBRIA2XWG5LUDACK24"),
+        ("2XS2367YHAXJFGLZHVAWLQD4ZY.nodes.example.org", "enr:-HW4QOFzoVLaFJnNhbgMoDXPnOvcdVuj7pDpqRvh6BRDO68aVi5ZcjB3vzQRZH2IcLBGHzo8uUN3snqmgTiE56CH3AMBgmlkgnY0iXNlY3AyNTZrMaECC2_24YYkYHEgdzxlSNKQEnHhuNAbNlMlWJxrJxbAFvA"),
+        ("H4FHT4B454P6UXFD7JCYQ5PWDY.nodes.example.org", "enr:-HW4QAggRauloj2SDLtIHN1XBkvhFZ1vtf1raYQp9TBW2RD5EEawDzbtSmlXUfnaHcvwOizhVYLtr7e6vw7NAf6mTuoCgmlkgnY0iXNlY3AyNTZrMaECjrXI8TLNXU0f8cthpAMxEshUyQlK-AM0PW2wfrnacNI"),
+        ("MHTDO6TMUBRIA2XWG5LUDACK24.nodes.example.org", "enr:-HW4QLAYqmrwllBEnzWWs7I5Ev2IAs7x_dZlbYdRdMUx5EyKHDXp7AV5CkuPGUPdvbv1_Ms1CPfhcGCvSElSosZmyoqAgmlkgnY0iXNlY3AyNTZrMaECriawHKWdDRk2xeZkrOXBQ0dfMFLHY4eENZwdufn1S1o"),
+    ];
+
+    struct StaticResolver(HashMap<String, String>);
+
+    impl StaticResolver {
+        fn new(entries: &[(&str, &str)]) -> Self {
+            StaticResolver(
+                entries
+                    .iter()
+                    .map(|(name, txt)| (name.to_string(), txt.to_string()))
+                    .collect(),
+            )
+        }
+    }
+
+    impl TxtResolver for StaticResolver {
+        async fn lookup_txt(&self, name: &str) -> Option<String> {
+            self.0.get(name).cloned()
+        }
+    }
+
+    #[tokio::test]
+    async fn resolve_eip_1459_tree() {
+        let link = TreeLink::from_str(TREE_LINK).unwrap();
+        let tree = resolve_tree(&StaticResolver::new(&TREE), &link)
+            .await
+            .unwrap();
+        assert_eq!(tree.seq, 1);
+        assert_eq!(tree.records.len(), 3);
+        assert_eq!(tree.links.len(), 1);
+        assert_eq!(tree.links[0].domain, "morenodes.example.org");
+    }
+
+    #[tokio::test]
+    async fn reject_tampered_trees() {
+        let link = TreeLink::from_str(TREE_LINK).unwrap();
+
+        let mut entries = TREE;
+        entries[0].1 = "enrtree-root:v1 e=JWXYDBPXYWG6FX3GMDIBFA6CJ4 l=C7HRFPF3BLGF3YR4DY5KX3SMBE seq=2 sig=o908WmNp7LibOfPsr4btQwatZJ5URBr2ZAuxvK4UWHlsB9sUOTJQaGAlLPVAhM__XJesCHxLISo94z5Z2a463gA";
+        assert_eq!(
+            resolve_tree(&StaticResolver::new(&entries), &link).await,
+            Err(DnsDiscoveryError::InvalidSignature)
+        );
+
+        let mut entries = TREE;
+        entries[2].1 = "enrtree-branch:2XS2367YHAXJFGLZHVAWLQD4ZY,H4FHT4B454P6UXFD7JCYQ5PWDY";
+        assert_eq!(
+            resolve_tree(&StaticResolver::new(&entries), &link).await,
+            Err(DnsDiscoveryError::HashMismatch(
+                "JWXYDBPXYWG6FX3GMDIBFA6CJ4.nodes.example.org".to_string()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn reject_truncated_root_signature() {
+        let link = TreeLink::from_str(TREE_LINK).unwrap();
+        let (content, signature) = TREE[0].1.split_once(" sig=").unwrap();
+        let signature = URL_SAFE_NO_PAD.decode(signature).unwrap();
+        let root = format!("{content} sig={}", URL_SAFE_NO_PAD.encode(&signature[..64]));
+
+        let mut entries = TREE;
+        entries[0].1 = &root;
+        assert_eq!(
+            resolve_tree(&StaticResolver::new(&entries), &link).await,
+            Err(DnsDiscoveryError::InvalidSignature)
+        );
+    }
+
+    #[tokio::test]
+    async fn reject_leaves_in_the_wrong_subtree() {
+        let signer = SigningKey::random(&mut OsRng);
+        let link = TreeLink {
+            public_key: *signer.verifying_key(),
+            domain: "nodes.example.org".to_string(),
+        };
+        let signed_root = |enr_root: &str, link_root: &str| {
+            let content = format!("enrtree-root:v1 e={enr_root} l={link_root} seq=1");
+            let (signature, recovery_id) = signer
+                .sign_prehash_recoverable(&keccak(&content).0)
+                .unwrap();
+            let mut signature = signature.to_vec();
+            signature.push(recovery_id.to_byte());
+            format!("{content} sig={}", URL_SAFE_NO_PAD.encode(signature))
+        };
+        // Record and link leaves from the EIP-1459 example tree
+        let (record_hash, link_hash) = ("2XS2367YHAXJFGLZHVAWLQD4ZY", "C7HRFPF3BLGF3YR4DY5KX3SMBE");
+
+        let root = signed_root(record_hash, link_hash);
+        let mut entries = TREE;
+        entries[0].1 = &root;
+        let tree = resolve_tree(&StaticResolver::new(&entries), &link)
+            .await
+            .unwrap();
+        assert_eq!(tree.records.len(), 1);
+        assert_eq!(tree.links.len(), 1);
+
+        let root = signed_root(link_hash, record_hash);
+        entries[0].1 = &root;
+        assert!(matches!(
+            resolve_tree(&StaticResolver::new(&entries), &link).await,
+            Err(DnsDiscoveryError::InvalidEntry(_))
+        ));
+    }
+
+    #[test]
+    fn parse_tree_links() {
+        assert!(TreeLink::from_str(TREE_LINK).is_ok());
+        assert_eq!(
+            TreeLink::from_str("enrtree://AKPYQIUQIL7PSIACI32J7FGZW56E5FKHEFCCOFHILBIMW3M6LWXS2"),
+            Err(DnsDiscoveryError::InvalidLink)
+        );
+        assert_eq!(
+            TreeLink::from_str("enrtree://nodes.example.org@AKPYQIUQIL7PSIACI32J7FGZW56E5"),
+            Err(DnsDiscoveryError::InvalidLink)
+        );
+    }
+}
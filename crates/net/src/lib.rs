@@ -1,4 +1,5 @@
 pub(crate) mod discv4;
+pub mod dns_discovery;
 
 use std::{
     fmt::Write,
@@ -7,38 +8,88 @@ use std::{
 };
 
 use discv4::{Endpoint, PingMessage};
-use k256::{ecdsa::SigningKey, elliptic_curve::rand_core::OsRng};
-use tokio::{
-    net::{TcpSocket, UdpSocket},
-    try_join,
-};
-use tracing::info;
+use dns_discovery::{resolve_tree, SystemTxtResolver, TreeLink};
+use ethrex_core::H512;
+use k256::ecdsa::SigningKey;
+use tokio::net::{TcpSocket, UdpSocket};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+use types::NodeRecord;
 pub mod types;
 
 const MAX_DISC_PACKET_SIZE: usize = 1280;
 
-pub async fn start_network(udp_addr: SocketAddr, tcp_addr: SocketAddr) {
+/// Runs the networking services until they finish or `cancel_token` is cancelled.
+pub async fn start_network(
+    udp_addr: SocketAddr,
+    tcp_addr: SocketAddr,
+    signer: SigningKey,
+    local_node_record: NodeRecord,
+    dns_trees: Vec<TreeLink>,
+    cancel_token: CancellationToken,
+) {
     info!("Starting discovery service at {udp_addr}");
     info!("Listening for requests at {tcp_addr}");
 
-    let discovery_handle = tokio::spawn(discover_peers(udp_addr));
-    let server_handle = tokio::spawn(serve_requests(tcp_addr));
-    try_join!(discovery_handle, server_handle).unwrap();
+    let services = async {
+        tokio::join!(
+            discover_peers(udp_addr, signer, local_node_record),
+            discover_dns_nodes(dns_trees),
+            serve_requests(tcp_addr)
+        )
+    };
+    tokio::select! {
+        _ = services => {}
+        _ = cancel_token.cancelled() => info!("Stopping networking services"),
+    }
 }
 
-async fn discover_peers(udp_addr: SocketAddr) {
+async fn discover_peers(udp_addr: SocketAddr, signer: SigningKey, local_node_record: NodeRecord) {
     let udp_socket = UdpSocket::bind(udp_addr).await.unwrap();
     // This is just a placeholder example. The address is a known bootnode.
     let receiver_addr: SocketAddr = ("138.197.51.181:30303").parse().unwrap();
     let mut buf = vec![0; MAX_DISC_PACKET_SIZE];
 
-    ping(&udp_socket, udp_addr, receiver_addr).await;
+    ping(
+        &udp_socket,
+        udp_addr,
+        receiver_addr,
+        &signer,
+        local_node_record.seq,
+    )
+    .await;
 
     let (read, from) = udp_socket.recv_from(&mut buf).await.unwrap();
     info!("Received {read} bytes from {from}");
     info!("Message: {}", to_hex(&buf[..read]));
 }
 
+/// Fetches the node lists published at the given DNS trees (EIP-1459).
+async fn discover_dns_nodes(trees: Vec<TreeLink>) {
+    if trees.is_empty() {
+        return;
+    }
+    let resolver = match SystemTxtResolver::new() {
+        Ok(resolver) => resolver,
+        Err(err) => {
+            warn!("Failed to set up the DNS resolver: {err}");
+            return;
+        }
+    };
+    for link in trees {
+        // TODO: add the nodes to the peer table once discovery keeps one
+        match resolve_tree(&resolver, &link).await {
+            Ok(tree) => info!(
+                "Found {} nodes and {} links in the DNS tree at {}",
+                tree.records.len(),
+                tree.links.len(),
+                link.domain
+            ),
+            Err(err) => warn!("Failed to resolve the DNS tree at {}: {err}", link.domain),
+        }
+    }
+}
+
 // TODO: maybe remove this
 fn to_hex(bytes: &[u8]) -> String {
     bytes.iter().fold(String::new(), |mut buf, b| {
@@ -47,7 +98,13 @@ fn to_hex(bytes: &[u8]) -> String {
     })
 }
 
-async fn ping(socket: &UdpSocket, local_addr: SocketAddr, to_addr: SocketAddr) {
+async fn ping(
+    socket: &UdpSocket,
+    local_addr: SocketAddr,
+    to_addr: SocketAddr,
+    signer: &SigningKey,
+    enr_seq: u64,
+) {
     let mut buf = Vec::new();
 
     let expiration: u64 = (SystemTime::now() + Duration::from_secs(10))
@@ -69,14 +126,46 @@ async fn ping(socket: &UdpSocket, local_addr: SocketAddr, to_addr: SocketAddr) {
         tcp_port: 0,
     };
 
-    let msg: discv4::Message = discv4::Message::Ping(PingMessage::new(from, to, expiration));
-    let signer = SigningKey::random(&mut OsRng);
-
+    let msg: discv4::Message =
+        discv4::Message::Ping(PingMessage::new(from, to, expiration).with_enr_seq(enr_seq));
     msg.encode_with_header(&mut buf, signer);
     socket.send_to(&buf, to_addr).await.unwrap();
 }
 
+/// Returns the node id derived from the node's secret key, which is its
+/// uncompressed public key without the leading SEC1 tag byte.
+pub fn node_id_from_signing_key(signer: &SigningKey) -> H512 {
+    let public_key = signer.verifying_key().to_encoded_point(false);
+    H512::from_slice(&public_key.as_bytes()[1..])
+}
+
 async fn serve_requests(tcp_addr: SocketAddr) {
-    let tcp_socket = TcpSocket::new_v4().unwrap();
+    // NOTE: binding to the IPv6 unspecified address (`::`) accepts both IPv4 and IPv6
+    // connections on dual-stack hosts
+    let tcp_socket = match tcp_addr {
+        SocketAddr::V4(_) => TcpSocket::new_v4(),
+        SocketAddr::V6(_) => TcpSocket::new_v6(),
+    }
+    .unwrap();
     tcp_socket.bind(tcp_addr).unwrap();
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn node_id_is_uncompressed_public_key() {
+        // The public key for the secret key `1` is the curve's generator point
+        let mut secret_key = [0; 32];
+        secret_key[31] = 1;
+        let signer = SigningKey::from_slice(&secret_key).unwrap();
+        let expected = H512::from_str(
+            "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+        )
+        .unwrap();
+        assert_eq!(node_id_from_signing_key(&signer), expected);
+    }
+}
@@ -1,29 +1,82 @@
 use ethrex_core::H512;
-use std::{net::SocketAddr, num::ParseIntError, str::FromStr};
+use std::{fmt::Display, net::SocketAddr, num::ParseIntError, str::FromStr};
+
+use super::Node;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BootNode {
     pub node_id: H512,
     pub socket_address: SocketAddr,
+    /// Port used for discovery, which is the same as the TCP port unless
+    /// the url specifies a different one.
+    pub udp_port: u16,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum BootNodeParseError {
+    InvalidFormat,
+    InvalidNodeId,
+    InvalidAddress,
 }
 
 impl FromStr for BootNode {
-    type Err = ParseIntError;
+    type Err = BootNodeParseError;
     /// Takes a str with the format "enode://nodeID@IPaddress:port" and
-    /// parses it to a BootNode
-    fn from_str(input: &str) -> Result<BootNode, ParseIntError> {
-        // TODO: error handling
-        let node_id = H512::from_str(&input[8..136]).expect("Failed to parse node id");
-        let socket_address: SocketAddr = input[137..]
+    /// parses it to a BootNode. A different discovery port may be given
+    /// as in "enode://nodeID@IPaddress:port?discport=udpPort"
+    fn from_str(input: &str) -> Result<BootNode, BootNodeParseError> {
+        let (node_id, address) = input
+            .strip_prefix("enode://")
+            .and_then(|s| s.split_once('@'))
+            .ok_or(BootNodeParseError::InvalidFormat)?;
+        if node_id.len() != 128 {
+            return Err(BootNodeParseError::InvalidNodeId);
+        }
+        let node_id = H512::from_str(node_id).map_err(|_| BootNodeParseError::InvalidNodeId)?;
+        let (socket_address, discport) = match address.split_once('?') {
+            Some((socket_address, query)) => {
+                let discport = query
+                    .strip_prefix("discport=")
+                    .and_then(|port| port.parse().ok())
+                    .ok_or(BootNodeParseError::InvalidFormat)?;
+                (socket_address, Some(discport))
+            }
+            None => (address, None),
+        };
+        let socket_address: SocketAddr = socket_address
             .parse()
-            .expect("Failed to parse bootnode address and port");
+            .map_err(|_| BootNodeParseError::InvalidAddress)?;
         Ok(BootNode {
             node_id,
             socket_address,
+            udp_port: discport.unwrap_or(socket_address.port()),
         })
     }
 }
 
+impl From<Node> for BootNode {
+    fn from(node: Node) -> Self {
+        BootNode {
+            node_id: node.node_id,
+            socket_address: SocketAddr::new(node.ip, node.tcp_port),
+            udp_port: node.udp_port,
+        }
+    }
+}
+
+impl Display for BootNode {
+    /// Formats the BootNode as "enode://nodeID@IPaddress:port".
+    /// IPv6 addresses are enclosed in brackets, as in "enode://nodeID@[::1]:port",
+    /// and the discovery port is appended when it differs from the TCP port
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "enode://{:x}@{}", self.node_id, self.socket_address)?;
+        if self.udp_port != self.socket_address.port() {
+            write!(f, "?discport={}", self.udp_port)?;
+        }
+        Ok(())
+    }
+}
+
 pub fn decode_hex(s: &str) -> Result<Vec<u8>, ParseIntError> {
     (0..s.len())
         .step_by(2)
@@ -42,6 +95,45 @@ fn parse_bootnode_from_string() {
     let expected_bootnode = BootNode {
         node_id,
         socket_address,
+        udp_port: 30303,
     };
     assert_eq!(bootnode, expected_bootnode);
 }
+
+#[test]
+fn parse_bootnode_with_discovery_port() {
+    let input = "enode://d860a01f9722d78051619d1e2351aba3f43f943f6f00718d1b9baa4101932a1f5011f16bb2b1bb35db20d6fe28fa0bf09636d26a87d31de9ec6203eeedb1f666@18.138.108.67:30303?discport=30301";
+    let bootnode = BootNode::from_str(input).unwrap();
+    assert_eq!(bootnode.socket_address.port(), 30303);
+    assert_eq!(bootnode.udp_port, 30301);
+    assert_eq!(bootnode.to_string(), input);
+}
+
+#[test]
+fn parse_ipv6_bootnode_from_string() {
+    let input = "enode://d860a01f9722d78051619d1e2351aba3f43f943f6f00718d1b9baa4101932a1f5011f16bb2b1bb35db20d6fe28fa0bf09636d26a87d31de9ec6203eeedb1f666@[2001:db8::1]:30303";
+    let bootnode = BootNode::from_str(input).unwrap();
+    let socket_address = SocketAddr::from_str("[2001:db8::1]:30303").unwrap();
+    assert_eq!(bootnode.socket_address, socket_address);
+    assert_eq!(bootnode.to_string(), input);
+}
+
+#[test]
+fn parse_invalid_bootnodes() {
+    assert_eq!(
+        BootNode::from_str("d860a01f@18.138.108.67:30303"),
+        Err(BootNodeParseError::InvalidFormat)
+    );
+    assert_eq!(
+        BootNode::from_str("enode://d860a01f@18.138.108.67:30303"),
+        Err(BootNodeParseError::InvalidNodeId)
+    );
+    assert_eq!(
+        BootNode::from_str("enode://d860a01f9722d78051619d1e2351aba3f43f943f6f00718d1b9baa4101932a1f5011f16bb2b1bb35db20d6fe28fa0bf09636d26a87d31de9ec6203eeedb1f666@18.138.108.67"),
+        Err(BootNodeParseError::InvalidAddress)
+    );
+    assert_eq!(
+        BootNode::from_str("enode://d860a01f9722d78051619d1e2351aba3f43f943f6f00718d1b9baa4101932a1f5011f16bb2b1bb35db20d6fe28fa0bf09636d26a87d31de9ec6203eeedb1f666@18.138.108.67:30303?discport=udp"),
+        Err(BootNodeParseError::InvalidFormat)
+    );
+}
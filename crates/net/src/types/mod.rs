@@ -1,2 +1,6 @@
 mod bootnode;
+mod node;
+mod node_record;
 pub use bootnode::*;
+pub use node::*;
+pub use node_record::*;
@@ -0,0 +1,74 @@
+use ethrex_core::H512;
+use std::net::IpAddr;
+
+use super::BootNode;
+
+/// Identity and advertised endpoints of a node in the network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Node {
+    pub ip: IpAddr,
+    pub udp_port: u16,
+    pub tcp_port: u16,
+    pub node_id: H512,
+}
+
+impl Node {
+    /// Returns the node's url in the "enode://nodeID@IPaddress:port" format.
+    /// The discovery port is appended as a query parameter when it differs from the TCP port.
+    pub fn enode_url(&self) -> String {
+        BootNode::from(*self).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{net::SocketAddr, str::FromStr};
+
+    use super::*;
+
+    #[test]
+    fn enode_url_includes_discport_only_when_needed() {
+        let node_id = H512::from_str(
+            "d860a01f9722d78051619d1e2351aba3f43f943f6f00718d1b9baa4101932a1f5011f16bb2b1bb35db20d6fe28fa0bf09636d26a87d31de9ec6203eeedb1f666")
+            .unwrap();
+        let mut node = Node {
+            ip: IpAddr::from_str("127.0.0.1").unwrap(),
+            udp_port: 30303,
+            tcp_port: 30303,
+            node_id,
+        };
+        assert_eq!(
+            node.enode_url(),
+            format!("enode://{node_id:x}@127.0.0.1:30303")
+        );
+
+        node.udp_port = 30301;
+        assert_eq!(
+            node.enode_url(),
+            format!("enode://{node_id:x}@127.0.0.1:30303?discport=30301")
+        );
+    }
+
+    #[test]
+    fn enode_url_roundtrip() {
+        let node_id = H512::from_str(
+            "d860a01f9722d78051619d1e2351aba3f43f943f6f00718d1b9baa4101932a1f5011f16bb2b1bb35db20d6fe28fa0bf09636d26a87d31de9ec6203eeedb1f666")
+            .unwrap();
+        for (ip, udp_port) in [("127.0.0.1", 30303), ("127.0.0.1", 30301), ("::1", 30301)] {
+            let node = Node {
+                ip: IpAddr::from_str(ip).unwrap(),
+                udp_port,
+                tcp_port: 30303,
+                node_id,
+            };
+            let bootnode = BootNode::from_str(&node.enode_url()).unwrap();
+            assert_eq!(bootnode.node_id, node.node_id);
+            assert_eq!(
+                bootnode.socket_address,
+                SocketAddr::new(node.ip, node.tcp_port)
+            );
+            assert_eq!(bootnode.udp_port, node.udp_port);
+            assert_eq!(bootnode.to_string(), node.enode_url());
+        }
+    }
+}
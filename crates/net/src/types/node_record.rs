@@ -0,0 +1,326 @@
+use std::{
+    fmt::Display,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use bytes::Bytes;
+use ethrex_core::{
+    hash::keccak,
+    rlp::{
+        decode::RLPDecode,
+        encode::RLPEncode,
+        structs::{Decoder, Encoder},
+    },
+    H264, H512,
+};
+use k256::ecdsa::{
+    signature::hazmat::{PrehashSigner, PrehashVerifier},
+    Signature, SigningKey, VerifyingKey,
+};
+
+use super::Node;
+
+/// Maximum size of an encoded node record, as defined by EIP-778.
+const MAX_RECORD_SIZE: usize = 300;
+
+/// Ethereum Node Record (EIP-778): a signed and versioned set of key/value pairs
+/// describing a node, using the "v4" identity scheme.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeRecord {
+    pub signature: H512,
+    /// Sequence number, increased every time the record changes.
+    pub seq: u64,
+    /// Key/value pairs sorted by key. Values are kept RLP-encoded, as their type
+    /// depends on the key and unknown keys must be preserved.
+    pairs: Vec<(Bytes, Bytes)>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum NodeRecordParseError {
+    InvalidFormat,
+    TooLarge,
+    InvalidSignature,
+}
+
+impl NodeRecord {
+    /// Creates a record for the given node, signed with the node's key.
+    /// Unspecified addresses (such as 0.0.0.0) can't be used to reach the node,
+    /// so only its ports are included for them.
+    pub fn new(node: &Node, seq: u64, signer: &SigningKey) -> Self {
+        let public_key = signer.verifying_key().to_encoded_point(true);
+        let mut pairs = vec![
+            encode_pair("id", &"v4"),
+            encode_pair("secp256k1", &H264::from_slice(public_key.as_bytes())),
+        ];
+        match node.ip {
+            ip if ip.is_unspecified() => pairs.extend([
+                encode_pair("tcp", &node.tcp_port),
+                encode_pair("udp", &node.udp_port),
+            ]),
+            IpAddr::V4(ip) => pairs.extend([
+                encode_pair("ip", &ip),
+                encode_pair("tcp", &node.tcp_port),
+                encode_pair("udp", &node.udp_port),
+            ]),
+            IpAddr::V6(ip) => pairs.extend([
+                encode_pair("ip6", &ip),
+                encode_pair("tcp6", &node.tcp_port),
+                encode_pair("udp6", &node.udp_port),
+            ]),
+        }
+        pairs.sort();
+        let mut record = NodeRecord {
+            signature: H512::zero(),
+            seq,
+            pairs,
+        };
+        record.sign(signer);
+        record
+    }
+
+    /// Returns the record for the node's current endpoints. The sequence number is only
+    /// increased when they differ from the ones in this record.
+    pub fn update(&self, node: &Node, signer: &SigningKey) -> Self {
+        let record = NodeRecord::new(node, self.seq, signer);
+        if record == *self {
+            record
+        } else {
+            NodeRecord::new(node, self.seq + 1, signer)
+        }
+    }
+
+    /// Returns the decoded value for the given key, if present and valid.
+    pub fn get<T: RLPDecode>(&self, key: &str) -> Option<T> {
+        self.pairs
+            .iter()
+            .find(|(k, _)| k.as_ref() == key.as_bytes())
+            .and_then(|(_, value)| T::decode(value).ok())
+    }
+
+    pub fn public_key(&self) -> Option<VerifyingKey> {
+        let key: H264 = self.get("secp256k1")?;
+        VerifyingKey::from_sec1_bytes(key.as_bytes()).ok()
+    }
+
+    /// Returns the node the record describes, if it has all the needed endpoints.
+    /// IPv4 endpoints are preferred over IPv6 ones.
+    pub fn to_node(&self) -> Option<Node> {
+        let public_key = self.public_key()?.to_encoded_point(false);
+        let node_id = H512::from_slice(&public_key.as_bytes()[1..]);
+        let (ip, tcp_port, udp_port) = match self.get::<Ipv4Addr>("ip") {
+            Some(ip) => (IpAddr::V4(ip), self.get("tcp")?, self.get("udp")?),
+            None => (
+                IpAddr::V6(self.get::<Ipv6Addr>("ip6")?),
+                self.get("tcp6")?,
+                self.get("udp6")?,
+            ),
+        };
+        Some(Node {
+            ip,
+            udp_port,
+            tcp_port,
+            node_id,
+        })
+    }
+
+    pub fn verify_signature(&self) -> bool {
+        if self.get::<String>("id").as_deref() != Some("v4") {
+            return false;
+        }
+        let (Some(public_key), Ok(signature)) = (
+            self.public_key(),
+            Signature::from_slice(self.signature.as_bytes()),
+        ) else {
+            return false;
+        };
+        public_key
+            .verify_prehash(&self.content_hash().0, &signature)
+            .is_ok()
+    }
+
+    fn sign(&mut self, signer: &SigningKey) {
+        let signature: Signature = signer
+            .sign_prehash(&self.content_hash().0)
+            .expect("failed to sign node record");
+        self.signature = H512::from_slice(&signature.to_bytes());
+    }
+
+    /// Hash of the record content, which is the RLP list `[seq, k, v, ...]`.
+    fn content_hash(&self) -> ethrex_core::H256 {
+        let mut buf = Vec::new();
+        self.encode_pairs(Encoder::new(&mut buf).encode_field(&self.seq))
+            .finish();
+        keccak(buf)
+    }
+
+    fn encode_pairs<'a>(&self, encoder: Encoder<'a>) -> Encoder<'a> {
+        self.pairs.iter().fold(encoder, |encoder, (key, value)| {
+            encoder.encode_field(key).encode_raw(value)
+        })
+    }
+}
+
+fn encode_pair<T: RLPEncode>(key: &str, value: &T) -> (Bytes, Bytes) {
+    let mut buf = Vec::new();
+    value.encode(&mut buf);
+    (Bytes::copy_from_slice(key.as_bytes()), Bytes::from(buf))
+}
+
+impl RLPEncode for NodeRecord {
+    fn encode(&self, buf: &mut dyn bytes::BufMut) {
+        self.encode_pairs(
+            Encoder::new(buf)
+                .encode_field(&self.signature)
+                .encode_field(&self.seq),
+        )
+        .finish();
+    }
+}
+
+impl RLPDecode for NodeRecord {
+    fn decode_unfinished(
+        rlp: &[u8],
+    ) -> Result<(Self, &[u8]), ethrex_core::rlp::error::RLPDecodeError> {
+        let decoder = Decoder::new(rlp)?;
+        let (signature, decoder) = decoder.decode_field("signature")?;
+        let (seq, mut decoder) = decoder.decode_field("seq")?;
+        let mut pairs = Vec::new();
+        while !decoder.is_done() {
+            let (key, next) = decoder.decode_field("key")?;
+            let (value, next) = next.get_encoded_item()?;
+            pairs.push((key, Bytes::copy_from_slice(value)));
+            decoder = next;
+        }
+        let record = NodeRecord {
+            signature,
+            seq,
+            pairs,
+        };
+        Ok((record, decoder.finish()?))
+    }
+}
+
+impl FromStr for NodeRecord {
+    type Err = NodeRecordParseError;
+    /// Parses a record in its text form, "enr:" followed by the URL-safe base64 of its RLP
+    /// encoding, checking its signature
+    fn from_str(input: &str) -> Result<NodeRecord, NodeRecordParseError> {
+        let rlp = input
+            .strip_prefix("enr:")
+            .and_then(|encoded| URL_SAFE_NO_PAD.decode(encoded).ok())
+            .ok_or(NodeRecordParseError::InvalidFormat)?;
+        if rlp.len() > MAX_RECORD_SIZE {
+            return Err(NodeRecordParseError::TooLarge);
+        }
+        let record = NodeRecord::decode(&rlp).map_err(|_| NodeRecordParseError::InvalidFormat)?;
+        let keys_sorted = record.pairs.windows(2).all(|pair| pair[0].0 < pair[1].0);
+        if !keys_sorted {
+            return Err(NodeRecordParseError::InvalidFormat);
+        }
+        if !record.verify_signature() {
+            return Err(NodeRecordParseError::InvalidSignature);
+        }
+        Ok(record)
+    }
+}
+
+impl Display for NodeRecord {
+    /// Formats the record in its text form, "enr:" followed by the URL-safe base64 of its RLP encoding
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut buf = Vec::new();
+        self.encode(&mut buf);
+        write!(f, "enr:{}", URL_SAFE_NO_PAD.encode(buf))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::decode_hex;
+
+    use super::*;
+
+    // Example record from EIP-778
+    const ENR: &str = "enr:-IS4QHCYrYZbAKWCBRlAy5zzaDZXJBGkcnh4MHcBFZntXNFrdvJjX04jRzjzCBOonrkTfj499SZuOh8R33Ls8RRcy5wBgmlkgnY0gmlwhH8AAAGJc2VjcDI1NmsxoQPKY0yuDUmstAHYpMa2_oxVtw0RW_QAdpzBQA8yWM0xOIN1ZHCCdl8";
+
+    fn signer() -> SigningKey {
+        let secret_key =
+            decode_hex("b71c71a67e1177ad4e901695e1b4b9ee17ae16c6668d313eac2f96dbcda3f291").unwrap();
+        SigningKey::from_slice(&secret_key).unwrap()
+    }
+
+    #[test]
+    fn parse_eip_778_record() {
+        let record = NodeRecord::from_str(ENR).unwrap();
+        assert_eq!(record.seq, 1);
+        assert_eq!(record.get::<String>("id").as_deref(), Some("v4"));
+        assert_eq!(
+            record.get::<Ipv4Addr>("ip"),
+            Some(Ipv4Addr::new(127, 0, 0, 1))
+        );
+        assert_eq!(record.get::<u16>("udp"), Some(30303));
+        assert_eq!(record.public_key().unwrap(), *signer().verifying_key());
+        // The record has no TCP port
+        assert_eq!(record.to_node(), None);
+        assert_eq!(record.to_string(), ENR);
+    }
+
+    #[test]
+    fn reject_invalid_records() {
+        assert_eq!(
+            NodeRecord::from_str("enode://1234"),
+            Err(NodeRecordParseError::InvalidFormat)
+        );
+        // Change the sequence number without signing the record again
+        let mut record = NodeRecord::from_str(ENR).unwrap();
+        record.seq = 2;
+        assert_eq!(
+            NodeRecord::from_str(&record.to_string()),
+            Err(NodeRecordParseError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn create_record_for_node() {
+        let signer = signer();
+        let node = Node {
+            ip: IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
+            udp_port: 30303,
+            tcp_port: 30304,
+            node_id: crate::node_id_from_signing_key(&signer),
+        };
+        let record = NodeRecord::new(&node, 1, &signer);
+        assert!(record.verify_signature());
+        assert_eq!(record.to_node(), Some(node));
+
+        let parsed = NodeRecord::from_str(&record.to_string()).unwrap();
+        assert_eq!(parsed, record);
+
+        // The sequence number only changes along with the record
+        assert_eq!(record.update(&node, &signer), record);
+        let moved = Node {
+            tcp_port: 30305,
+            ..node
+        };
+        let updated = record.update(&moved, &signer);
+        assert_eq!(updated.seq, 2);
+        assert_eq!(updated.to_node(), Some(moved));
+    }
+
+    #[test]
+    fn unspecified_address_is_left_out() {
+        let signer = signer();
+        let node = Node {
+            ip: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            udp_port: 30303,
+            tcp_port: 30303,
+            node_id: crate::node_id_from_signing_key(&signer),
+        };
+        let record = NodeRecord::new(&node, 1, &signer);
+        assert_eq!(record.get::<Ipv4Addr>("ip"), None);
+        assert_eq!(record.get::<u16>("tcp"), Some(30303));
+        assert_eq!(record.get::<u16>("udp"), Some(30303));
+        assert_eq!(record.to_node(), None);
+    }
+}
@@ -0,0 +1,25 @@
+use std::{path::Path, process::Command};
+
+/// Exposes build metadata reported by `web3_clientVersion` as compile-time env vars.
+fn main() {
+    let commit = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|commit| commit.trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=ETHREX_GIT_COMMIT={commit}");
+
+    let target = std::env::var("TARGET").unwrap_or_else(|_| "unknown".to_string());
+    println!("cargo:rustc-env=ETHREX_TARGET={target}");
+
+    // Only rebuild when the checked out commit changes
+    for path in ["../../.git/HEAD", "../../.git/refs/heads"] {
+        if Path::new(path).exists() {
+            println!("cargo:rerun-if-changed={path}");
+        }
+    }
+    println!("cargo:rerun-if-changed=build.rs");
+}
@@ -1,21 +1,28 @@
+use ethrex_net::types::{Node, NodeRecord};
 use serde_json::{json, Value};
 
-use crate::utils::RpcErr;
+use crate::{utils::RpcErr, web3::CLIENT_VERSION};
 
-pub fn node_info() -> Result<Value, RpcErr> {
+/// Returns the local node's identity and endpoints. `protocols` is empty
+/// because the node doesn't run any RLPx subprotocol yet.
+pub fn node_info(local_node: &Node, local_node_record: &NodeRecord) -> Result<Value, RpcErr> {
     Ok(json!({
-        "enode": "enode://pubkey@ip:port",
-        "id": "pubkey",
-        "name": "node",
+        "enode": local_node.enode_url(),
+        "enr": local_node_record.to_string(),
+        "id": format!("{:x}", local_node.node_id),
+        "ip": local_node.ip,
+        "name": CLIENT_VERSION,
         "ports": {
-            "discovery": 1234,
-            "listener": 1234,
-        },
-        "protocols": {
-            "eth": {
-                "network": 1234,
-                "version": 1234,
-            },
+            "discovery": local_node.udp_port,
+            "listener": local_node.tcp_port,
         },
+        "protocols": {},
     }))
 }
+
+/// Returns the connected peers. Not supported yet, as the p2p layer doesn't
+/// keep track of the peers it is connected to.
+pub fn peers() -> Result<Value, RpcErr> {
+    // TODO: report connected peers once the p2p layer keeps a peer table
+    Err(RpcErr::Internal("admin_peers is not supported".to_string()))
+}
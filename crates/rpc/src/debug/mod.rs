@@ -0,0 +1,7 @@
+use serde_json::Value;
+
+use crate::{journal::EventJournal, utils::RpcErr};
+
+pub fn get_event_journal(journal: &EventJournal) -> Result<Value, RpcErr> {
+    serde_json::to_value(journal.entries()).map_err(|err| RpcErr::Internal(err.to_string()))
+}
@@ -1,15 +1,61 @@
 use serde_json::{json, Value};
-use tracing::info;
+use tracing::{info, warn};
 
-use crate::RpcErr;
+use crate::{
+    first_param,
+    journal::{Event, EventJournal},
+    utils::RpcRequest,
+    RpcErr,
+};
 
 pub type ExchangeCapabilitiesRequest = Vec<String>;
 
+type EngineHandler = fn(&RpcRequest, &EventJournal) -> Result<Value, RpcErr>;
+
+/// Engine API methods served by the Auth-RPC endpoint, along with their handlers.
+/// The router and the capabilities reported to the consensus client both come
+/// from this table, so they can't drift apart. `engine_exchangeCapabilities` is
+/// routed on its own, as the spec requires leaving it out of the capabilities.
+pub const METHODS: &[(&str, EngineHandler)] = &[
+    ("engine_forkchoiceUpdatedV3", |req, journal| {
+        forkchoice_updated_v3(first_param(req)?, journal)
+    }),
+    ("engine_newPayloadV3", |req, journal| {
+        new_payload_v3(first_param(req)?, journal)
+    }),
+];
+
+/// Returns the handler of an engine method, if the node serves it.
+pub fn handler(method: &str) -> Option<EngineHandler> {
+    METHODS
+        .iter()
+        .find(|(name, _)| *name == method)
+        .map(|(_, handler)| *handler)
+}
+
 pub fn exchange_capabilities(capabilities: &ExchangeCapabilitiesRequest) -> Result<Value, RpcErr> {
-    Ok(json!(capabilities))
+    let unsupported: Vec<&String> = capabilities
+        .iter()
+        .filter(|method| handler(method).is_none())
+        .collect();
+    if !unsupported.is_empty() {
+        warn!("Consensus client requested unsupported engine methods: {unsupported:?}");
+    }
+    let served: Vec<&str> = METHODS.iter().map(|(name, _)| *name).collect();
+    Ok(json!(served))
 }
 
-pub fn forkchoice_updated_v3() -> Result<Value, RpcErr> {
+pub fn forkchoice_updated_v3(
+    fork_choice_state: &Value,
+    journal: &EventJournal,
+) -> Result<Value, RpcErr> {
+    journal.record(Event::ForkchoiceUpdated {
+        head_block_hash: fork_choice_state["headBlockHash"].clone(),
+        safe_block_hash: fork_choice_state["safeBlockHash"].clone(),
+        finalized_block_hash: fork_choice_state["finalizedBlockHash"].clone(),
+        status: "SYNCING".to_string(),
+    });
+
     Ok(json!({
         "payloadId": null,
         "payloadStatus": {
@@ -20,12 +66,18 @@ pub fn forkchoice_updated_v3() -> Result<Value, RpcErr> {
     }))
 }
 
-pub fn new_payload_v3(block: &Value) -> Result<Value, RpcErr> {
+pub fn new_payload_v3(block: &Value, journal: &EventJournal) -> Result<Value, RpcErr> {
     info!(
         "Received new payload with block hash: {}",
         block["blockHash"]
     );
 
+    journal.record(Event::NewPayload {
+        block_hash: block["blockHash"].clone(),
+        status: "SYNCING".to_string(),
+        validation_error: None,
+    });
+
     Ok(json!({
         "latestValidHash": null,
         "status": "SYNCING",
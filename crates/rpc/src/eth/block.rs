@@ -1,7 +1,71 @@
+use ethrex_core::types::BlockNumber;
 use serde_json::Value;
 
 use crate::utils::RpcErr;
 
-pub fn get_block_by_number() -> Result<Value, RpcErr> {
-    Ok(Value::Null)
+/// Block parameter accepted by the block-taking endpoints: either a
+/// hex-encoded block number or one of the default block tags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockIdentifier {
+    Number(BlockNumber),
+    Tag(BlockTag),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockTag {
+    Earliest,
+    Finalized,
+    Safe,
+    Latest,
+    Pending,
+}
+
+impl BlockIdentifier {
+    pub fn parse(value: &Value) -> Result<Self, RpcErr> {
+        let value = value.as_str().ok_or(RpcErr::BadParams(
+            "expected a block number or tag".to_string(),
+        ))?;
+        let identifier = match value {
+            "earliest" => BlockIdentifier::Tag(BlockTag::Earliest),
+            "finalized" => BlockIdentifier::Tag(BlockTag::Finalized),
+            "safe" => BlockIdentifier::Tag(BlockTag::Safe),
+            "latest" => BlockIdentifier::Tag(BlockTag::Latest),
+            "pending" => BlockIdentifier::Tag(BlockTag::Pending),
+            number => BlockIdentifier::Number(parse_quantity(number)?),
+        };
+        Ok(identifier)
+    }
+}
+
+fn parse_quantity(value: &str) -> Result<u64, RpcErr> {
+    let invalid = || RpcErr::BadParams(format!("invalid hex quantity {value}"));
+    let hex = value.strip_prefix("0x").ok_or_else(invalid)?;
+    u64::from_str_radix(hex, 16).map_err(|_| invalid())
+}
+
+pub fn get_block_by_number(_block: &BlockIdentifier, _hydrated: bool) -> Result<Value, RpcErr> {
+    // TODO: resolve block tags from the Store's chain data and fetch the block from storage
+    Err(RpcErr::Internal(
+        "eth_getBlockByNumber is not supported".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_block_identifiers() {
+        assert_eq!(
+            BlockIdentifier::parse(&Value::from("0x1b4")).unwrap(),
+            BlockIdentifier::Number(436)
+        );
+        assert_eq!(
+            BlockIdentifier::parse(&Value::from("safe")).unwrap(),
+            BlockIdentifier::Tag(BlockTag::Safe)
+        );
+        assert!(BlockIdentifier::parse(&Value::from("1b4")).is_err());
+        assert!(BlockIdentifier::parse(&Value::from("newest")).is_err());
+        assert!(BlockIdentifier::parse(&Value::from(436)).is_err());
+    }
 }
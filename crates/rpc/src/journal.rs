@@ -0,0 +1,108 @@
+use std::{
+    collections::VecDeque,
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// Maximum amount of events kept by the journal, older events are discarded first.
+pub const MAX_JOURNAL_EVENTS: usize = 1024;
+
+/// Decision taken by the node while following the consensus client.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Event {
+    #[serde(rename_all = "camelCase")]
+    ForkchoiceUpdated {
+        head_block_hash: Value,
+        safe_block_hash: Value,
+        finalized_block_hash: Value,
+        status: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    NewPayload {
+        block_hash: Value,
+        status: String,
+        validation_error: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JournalEntry {
+    /// Unix time in milliseconds at which the event was recorded.
+    pub timestamp: u128,
+    pub event: Event,
+}
+
+/// Append-only, size-bounded record of chain head changes and payload decisions,
+/// used for post-mortem analysis of consensus incidents.
+#[derive(Debug)]
+pub struct EventJournal {
+    entries: Mutex<VecDeque<JournalEntry>>,
+    capacity: usize,
+}
+
+impl EventJournal {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity,
+        }
+    }
+
+    pub fn record(&self, event: Event) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or_default();
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() == self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(JournalEntry { timestamp, event });
+    }
+
+    /// Returns the recorded entries, oldest first.
+    pub fn entries(&self) -> Vec<JournalEntry> {
+        self.entries.lock().unwrap().iter().cloned().collect()
+    }
+}
+
+impl Default for EventJournal {
+    fn default() -> Self {
+        Self::new(MAX_JOURNAL_EVENTS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_payload(block_hash: &str) -> Event {
+        Event::NewPayload {
+            block_hash: Value::String(block_hash.to_string()),
+            status: "SYNCING".to_string(),
+            validation_error: None,
+        }
+    }
+
+    #[test]
+    fn journal_discards_oldest_events() {
+        let journal = EventJournal::new(2);
+        journal.record(new_payload("0x01"));
+        journal.record(new_payload("0x02"));
+        journal.record(new_payload("0x03"));
+
+        let hashes: Vec<_> = journal
+            .entries()
+            .into_iter()
+            .map(|entry| match entry.event {
+                Event::NewPayload { block_hash, .. } => block_hash,
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(hashes, vec!["0x02", "0x03"]);
+    }
+}
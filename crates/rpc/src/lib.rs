@@ -1,31 +1,92 @@
-use std::{future::IntoFuture, net::SocketAddr};
+use std::{future::IntoFuture, net::SocketAddr, sync::Arc, time::Instant};
 
-use axum::{routing::post, Json, Router};
+use axum::{
+    extract::{ConnectInfo, State},
+    http::{header, HeaderValue, Method, StatusCode},
+    response::{IntoResponse, Response},
+    routing::post,
+    Json, Router,
+};
 use engine::ExchangeCapabilitiesRequest;
-use eth::{block, client};
+use eth::{
+    block::{self, BlockIdentifier},
+    client,
+};
+use ethrex_core::U256;
+use ethrex_net::types::{Node, NodeRecord};
+use journal::EventJournal;
+use limits::{RateLimiter, RpcLimits};
 use serde_json::Value;
 use tokio::net::TcpListener;
-use tracing::info;
-use utils::{RpcErr, RpcErrorMetadata, RpcErrorResponse, RpcRequest, RpcSuccessResponse};
+use tokio_util::sync::CancellationToken;
+use tower_http::cors::{Any, CorsLayer};
+use tracing::{debug, debug_span, info, warn};
+use utils::{
+    RpcErr, RpcErrorMetadata, RpcErrorResponse, RpcRequest, RpcRequestId, RpcSuccessResponse,
+};
 
 mod admin;
+mod debug;
 mod engine;
 mod eth;
+mod journal;
+pub mod limits;
+mod net;
 mod utils;
+mod web3;
 
-pub async fn start_api(http_addr: SocketAddr, authrpc_addr: SocketAddr) {
-    let http_router = Router::new().route("/", post(handle_http_request));
+/// State shared by the handlers of both RPC servers.
+#[derive(Debug, Clone)]
+pub struct RpcApiContext {
+    journal: Arc<EventJournal>,
+    local_node: Node,
+    local_node_record: NodeRecord,
+    chain_id: U256,
+    /// Limits applied to the public HTTP endpoint.
+    limits: Arc<RpcLimits>,
+    rate_limiter: Arc<RateLimiter>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn start_api(
+    http_addr: SocketAddr,
+    authrpc_addr: SocketAddr,
+    local_node: Node,
+    local_node_record: NodeRecord,
+    chain_id: U256,
+    limits: RpcLimits,
+    cors_allowed_origins: Vec<String>,
+    cancel_token: CancellationToken,
+) {
+    let context = RpcApiContext {
+        journal: Arc::new(EventJournal::default()),
+        local_node,
+        local_node_record,
+        chain_id,
+        limits: Arc::new(limits),
+        rate_limiter: Arc::new(RateLimiter::default()),
+    };
+
+    let http_router = Router::new()
+        .route("/", post(handle_http_request))
+        .layer(cors_layer(&cors_allowed_origins))
+        .with_state(context.clone());
     let http_listener = TcpListener::bind(http_addr).await.unwrap();
 
-    let authrpc_router = Router::new().route("/", post(handle_authrpc_request));
+    let authrpc_router = Router::new()
+        .route("/", post(handle_authrpc_request))
+        .with_state(context);
     let authrpc_listener = TcpListener::bind(authrpc_addr).await.unwrap();
 
     let authrpc_server = axum::serve(authrpc_listener, authrpc_router)
-        .with_graceful_shutdown(shutdown_signal())
-        .into_future();
-    let http_server = axum::serve(http_listener, http_router)
-        .with_graceful_shutdown(shutdown_signal())
+        .with_graceful_shutdown(cancel_token.clone().cancelled_owned())
         .into_future();
+    let http_server = axum::serve(
+        http_listener,
+        http_router.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(cancel_token.cancelled_owned())
+    .into_future();
 
     info!("Starting HTTP server at {http_addr}");
     info!("Starting Auth-RPC server at {}", authrpc_addr);
@@ -34,81 +95,443 @@ pub async fn start_api(http_addr: SocketAddr, authrpc_addr: SocketAddr) {
         .inspect_err(|e| info!("Error shutting down servers: {:?}", e));
 }
 
-async fn shutdown_signal() {
-    tokio::signal::ctrl_c()
-        .await
-        .expect("failed to install Ctrl+C handler");
+/// Builds the CORS policy for the HTTP endpoint, so browser dapps served from
+/// `allowed_origins` can call it. A `*` origin allows requests from any origin.
+fn cors_layer(allowed_origins: &[String]) -> CorsLayer {
+    let layer = CorsLayer::new()
+        .allow_methods([Method::POST])
+        .allow_headers([header::CONTENT_TYPE]);
+    if allowed_origins.iter().any(|origin| origin == "*") {
+        layer.allow_origin(Any)
+    } else {
+        let origins: Vec<HeaderValue> = allowed_origins
+            .iter()
+            .filter_map(|origin| HeaderValue::from_str(origin).ok())
+            .collect();
+        layer.allow_origin(origins)
+    }
 }
 
-pub async fn handle_authrpc_request(body: String) -> Json<Value> {
-    let req: RpcRequest = serde_json::from_str(&body).unwrap();
-    let res = map_requests(&req);
-    rpc_response(req.id, res)
+pub async fn handle_authrpc_request(
+    State(context): State<RpcApiContext>,
+    body: String,
+) -> Response {
+    // The HTTP limits don't apply to the consensus client, but slow requests are still reported
+    let limits = RpcLimits {
+        slow_request_threshold: context.limits.slow_request_threshold,
+        ..Default::default()
+    };
+    http_response(handle_request_body(
+        &body,
+        &limits,
+        |_| true,
+        |req| map_requests(req, &context.journal),
+    ))
 }
 
-pub fn map_requests(req: &RpcRequest) -> Result<Value, RpcErr> {
+pub fn map_requests(req: &RpcRequest, journal: &EventJournal) -> Result<Value, RpcErr> {
     match req.method.as_str() {
         "engine_exchangeCapabilities" => {
-            let capabilities: ExchangeCapabilitiesRequest = req
-                .params
-                .as_ref()
-                .ok_or(RpcErr::BadParams)?
-                .first()
-                .ok_or(RpcErr::BadParams)
-                .and_then(|v| serde_json::from_value(v.clone()).map_err(|_| RpcErr::BadParams))?;
+            let capabilities: ExchangeCapabilitiesRequest =
+                serde_json::from_value(first_param(req)?.clone())
+                    .map_err(|_| RpcErr::BadParams("expected a list of methods".to_string()))?;
             engine::exchange_capabilities(&capabilities)
         }
         "eth_chainId" => client::chain_id(),
         "eth_syncing" => client::syncing(),
-        "eth_getBlockByNumber" => block::get_block_by_number(),
-        "engine_forkchoiceUpdatedV3" => engine::forkchoice_updated_v3(),
-        "engine_newPayloadV3" => {
-            let block = req
-                .params
-                .as_ref()
-                .ok_or(RpcErr::BadParams)?
-                .first()
-                .ok_or(RpcErr::BadParams)?;
-            engine::new_payload_v3(block)
+        "eth_getBlockByNumber" => {
+            let (block, hydrated) = parse_get_block_by_number_params(req)?;
+            block::get_block_by_number(&block, hydrated)
         }
-        _ => Err(RpcErr::MethodNotFound),
+        method => match engine::handler(method) {
+            Some(handler) => handler(req, journal),
+            None => Err(RpcErr::MethodNotFound(method.to_string())),
+        },
     }
 }
 
-pub async fn handle_http_request(body: String) -> Json<Value> {
-    let req: RpcRequest = serde_json::from_str(&body).unwrap();
+pub async fn handle_http_request(
+    State(context): State<RpcApiContext>,
+    ConnectInfo(client_addr): ConnectInfo<SocketAddr>,
+    body: String,
+) -> Response {
+    let limits = &context.limits;
+    let admit = |requests: usize| match limits.requests_per_second {
+        Some(limit) => context.rate_limiter.check(
+            client_addr.ip(),
+            requests.try_into().unwrap_or(u32::MAX),
+            limit,
+        ),
+        None => true,
+    };
+    http_response(handle_request_body(&body, limits, admit, |req| {
+        map_http_requests(req, &context)
+    }))
+}
 
-    let res: Result<Value, RpcErr> = match req.method.as_str() {
+/// Bodies made only of notifications get an empty response.
+fn http_response(response: Option<Value>) -> Response {
+    match response {
+        Some(response) => Json(response).into_response(),
+        None => StatusCode::OK.into_response(),
+    }
+}
+
+pub fn map_http_requests(req: &RpcRequest, context: &RpcApiContext) -> Result<Value, RpcErr> {
+    match req.method.as_str() {
         "eth_chainId" => client::chain_id(),
         "eth_syncing" => client::syncing(),
-        "eth_getBlockByNumber" => block::get_block_by_number(),
-        "admin_nodeInfo" => admin::node_info(),
-        _ => Err(RpcErr::MethodNotFound),
+        "eth_getBlockByNumber" => {
+            let (block, hydrated) = parse_get_block_by_number_params(req)?;
+            block::get_block_by_number(&block, hydrated)
+        }
+        "admin_nodeInfo" => admin::node_info(&context.local_node, &context.local_node_record),
+        "admin_peers" => admin::peers(),
+        "debug_getEventJournal" => debug::get_event_journal(&context.journal),
+        "net_version" => net::version(context.chain_id),
+        "web3_clientVersion" => web3::client_version(),
+        "web3_sha3" => {
+            let [data] = params::<1>(req)?;
+            web3::sha3(data)
+        }
+        method => Err(RpcErr::MethodNotFound(method.to_string())),
+    }
+}
+
+/// Returns the request params, failing if there isn't exactly `N` of them.
+fn params<const N: usize>(req: &RpcRequest) -> Result<&[Value; N], RpcErr> {
+    req.params
+        .as_deref()
+        .unwrap_or_default()
+        .try_into()
+        .map_err(|_| RpcErr::BadParams(format!("expected {N} params")))
+}
+
+/// Returns the first request param, ignoring the rest.
+fn first_param(req: &RpcRequest) -> Result<&Value, RpcErr> {
+    req.params
+        .as_deref()
+        .and_then(|params| params.first())
+        .ok_or(RpcErr::BadParams("missing params".to_string()))
+}
+
+fn parse_get_block_by_number_params(req: &RpcRequest) -> Result<(BlockIdentifier, bool), RpcErr> {
+    let [block, hydrated] = params::<2>(req)?;
+    let block = BlockIdentifier::parse(block)?;
+    let hydrated = hydrated
+        .as_bool()
+        .ok_or(RpcErr::BadParams("expected a boolean".to_string()))?;
+    Ok((block, hydrated))
+}
+
+/// Handles a request body that may hold either a single request or a batch of them.
+/// Each request in a batch gets its own response, so an invalid or failing entry
+/// doesn't affect the rest of the batch. Notifications are handled without a response,
+/// so a body made only of notifications gets none at all.
+/// `admit` is called with the amount of requests in the body before handling any of them,
+/// and rejects all of them if it returns false.
+fn handle_request_body<A, F>(body: &str, limits: &RpcLimits, admit: A, handler: F) -> Option<Value>
+where
+    A: FnOnce(usize) -> bool,
+    F: Fn(&RpcRequest) -> Result<Value, RpcErr>,
+{
+    let Ok(body) = serde_json::from_str::<Value>(body) else {
+        return Some(rpc_error_response(None, RpcErr::ParseError));
+    };
+    match body {
+        Value::Array(requests) if requests.is_empty() => {
+            Some(rpc_error_response(None, RpcErr::InvalidRequest))
+        }
+        Value::Array(requests)
+            if limits
+                .max_batch_size
+                .is_some_and(|max_batch_size| requests.len() > max_batch_size) =>
+        {
+            Some(rpc_error_response(
+                None,
+                RpcErr::LimitExceeded("too many requests in batch".to_string()),
+            ))
+        }
+        Value::Array(requests) => {
+            if !admit(requests.len()) {
+                return Some(rpc_error_response(None, rate_limit_exceeded()));
+            }
+            let responses: Vec<Value> = requests
+                .into_iter()
+                .filter_map(|req| handle_single_request(req, limits, &handler))
+                .collect();
+            if responses.is_empty() {
+                return None;
+            }
+            let responses = Value::Array(responses);
+            // Every result is within the limit, but together they may still exceed it
+            if exceeds_max_response_size(limits, &responses) {
+                return Some(rpc_error_response(None, response_too_large()));
+            }
+            Some(responses)
+        }
+        req => {
+            if !admit(1) {
+                return Some(rpc_error_response(None, rate_limit_exceeded()));
+            }
+            handle_single_request(req, limits, &handler)
+        }
+    }
+}
+
+fn rate_limit_exceeded() -> RpcErr {
+    RpcErr::LimitExceeded("too many requests".to_string())
+}
+
+fn response_too_large() -> RpcErr {
+    RpcErr::LimitExceeded("response too large".to_string())
+}
+
+fn exceeds_max_response_size(limits: &RpcLimits, response: &Value) -> bool {
+    limits.max_response_size.is_some_and(|max_response_size| {
+        serde_json::to_vec(response).map_or(0, |r| r.len()) > max_response_size
+    })
+}
+
+/// Handles a single request, returning its response unless it is a notification.
+fn handle_single_request<F>(req: Value, limits: &RpcLimits, handler: &F) -> Option<Value>
+where
+    F: Fn(&RpcRequest) -> Result<Value, RpcErr>,
+{
+    // Requests without an id are notifications, while a null id still gets a response
+    let is_notification = req.as_object().is_some_and(|req| !req.contains_key("id"));
+    let Ok(req) = serde_json::from_value::<RpcRequest>(req) else {
+        return Some(rpc_error_response(None, RpcErr::InvalidRequest));
     };
+    let span = debug_span!("rpc_request", method = %req.method, id = ?req.id);
+    let _guard = span.enter();
+    if !limits.is_method_allowed(&req.method) {
+        let response = rpc_error_response(req.id, RpcErr::MethodNotFound(req.method));
+        return (!is_notification).then_some(response);
+    }
+    let start = Instant::now();
+    let res = handler(&req).and_then(|result| {
+        if exceeds_max_response_size(limits, &result) {
+            Err(response_too_large())
+        } else {
+            Ok(result)
+        }
+    });
+    let elapsed = start.elapsed();
+    debug!(
+        params_size = params_size(&req),
+        outcome = outcome(&res),
+        "Handled request in {elapsed:?}"
+    );
+    if limits
+        .slow_request_threshold
+        .is_some_and(|threshold| elapsed > threshold)
+    {
+        warn!(
+            params_size = params_size(&req),
+            outcome = outcome(&res),
+            "Slow request {} took {elapsed:?}",
+            req.method
+        );
+    }
+    (!is_notification).then(|| rpc_response(req.id, res))
+}
 
-    rpc_response(req.id, res)
+/// Size in bytes of the JSON-encoded request params, for logging.
+fn params_size(req: &RpcRequest) -> usize {
+    req.params.as_ref().map_or(0, |params| {
+        serde_json::to_vec(params).map_or(0, |p| p.len())
+    })
 }
 
-fn rpc_response<E>(id: i32, res: Result<Value, E>) -> Json<Value>
+/// Short description of a request result, for logging.
+fn outcome(res: &Result<Value, RpcErr>) -> String {
+    match res {
+        Ok(_) => "ok".to_string(),
+        Err(err) => format!("error {}", RpcErrorMetadata::from(err.clone()).code),
+    }
+}
+
+fn rpc_response<E>(id: Option<RpcRequestId>, res: Result<Value, E>) -> Value
 where
     E: Into<RpcErrorMetadata>,
 {
     match res {
-        Ok(result) => Json(
-            serde_json::to_value(RpcSuccessResponse {
-                id,
-                jsonrpc: "2.0".to_string(),
-                result,
-            })
-            .unwrap(),
-        ),
-        Err(error) => Json(
-            serde_json::to_value(RpcErrorResponse {
-                id,
+        Ok(result) => serde_json::to_value(RpcSuccessResponse {
+            id,
+            jsonrpc: "2.0".to_string(),
+            result,
+        })
+        .unwrap(),
+        Err(error) => rpc_error_response(id, error),
+    }
+}
+
+fn rpc_error_response<E>(id: Option<RpcRequestId>, error: E) -> Value
+where
+    E: Into<RpcErrorMetadata>,
+{
+    serde_json::to_value(RpcErrorResponse {
+        id,
+        jsonrpc: "2.0".to_string(),
+        error: error.into(),
+    })
+    .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn handler(req: &RpcRequest) -> Result<Value, RpcErr> {
+        match req.method.as_str() {
+            "test_ok" => Ok(Value::Bool(true)),
+            method => Err(RpcErr::MethodNotFound(method.to_string())),
+        }
+    }
+
+    #[test]
+    fn single_request() {
+        let body = r#"{"jsonrpc":"2.0","id":1,"method":"test_ok","params":[]}"#;
+        assert_eq!(
+            handle_request_body(body, &RpcLimits::default(), |_| true, handler),
+            Some(json!({"jsonrpc": "2.0", "id": 1, "result": true}))
+        );
+    }
+
+    #[test]
+    fn batch_request_isolates_errors() {
+        let body = r#"[
+            {"jsonrpc":"2.0","id":1,"method":"test_ok","params":[]},
+            {"jsonrpc":"2.0","id":"two","method":"test_unknown","params":[]},
+            {"foo":"bar"}
+        ]"#;
+        assert_eq!(
+            handle_request_body(body, &RpcLimits::default(), |_| true, handler),
+            Some(json!([
+                {"jsonrpc": "2.0", "id": 1, "result": true},
+                {"jsonrpc": "2.0", "id": "two", "error": {"code": -32601, "message": "The method test_unknown does not exist/is not available"}},
+                {"jsonrpc": "2.0", "id": null, "error": {"code": -32600, "message": "Invalid request"}},
+            ]))
+        );
+    }
+
+    #[test]
+    fn limits_are_enforced() {
+        let body = r#"[
+            {"jsonrpc":"2.0","id":1,"method":"test_ok","params":[]},
+            {"jsonrpc":"2.0","id":2,"method":"test_ok","params":[]}
+        ]"#;
+        let limits = RpcLimits {
+            max_batch_size: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            handle_request_body(body, &limits, |_| true, handler).unwrap()["error"]["code"],
+            json!(-32005)
+        );
+        assert_eq!(
+            handle_request_body(body, &RpcLimits::default(), |n| n < 2, handler).unwrap()["error"]
+                ["code"],
+            json!(-32005)
+        );
+
+        let limits = RpcLimits {
+            denied_methods: vec!["test_*".to_string()],
+            ..Default::default()
+        };
+        let body = r#"{"jsonrpc":"2.0","id":1,"method":"test_ok","params":[]}"#;
+        assert_eq!(
+            handle_request_body(body, &limits, |_| true, handler).unwrap()["error"]["code"],
+            json!(-32601)
+        );
+
+        let limits = RpcLimits {
+            max_response_size: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            handle_request_body(body, &limits, |_| true, handler).unwrap()["error"]["code"],
+            json!(-32005)
+        );
+
+        // Each result fits within the limit, but the whole batch response doesn't
+        let body = r#"[
+            {"jsonrpc":"2.0","id":1,"method":"test_ok","params":[]},
+            {"jsonrpc":"2.0","id":2,"method":"test_ok","params":[]}
+        ]"#;
+        let limits = RpcLimits {
+            max_response_size: Some(40),
+            ..Default::default()
+        };
+        assert_eq!(
+            handle_request_body(body, &limits, |_| true, handler).unwrap()["error"]["code"],
+            json!(-32005)
+        );
+    }
+
+    #[test]
+    fn notifications_get_no_response() {
+        let notification = r#"{"jsonrpc":"2.0","method":"test_ok","params":[]}"#;
+        assert_eq!(
+            handle_request_body(notification, &RpcLimits::default(), |_| true, handler),
+            None
+        );
+
+        let body = format!("[{notification}, {notification}]");
+        assert_eq!(
+            handle_request_body(&body, &RpcLimits::default(), |_| true, handler),
+            None
+        );
+
+        let body = format!(
+            r#"[{notification}, {{"jsonrpc":"2.0","id":null,"method":"test_ok","params":[]}}]"#
+        );
+        assert_eq!(
+            handle_request_body(&body, &RpcLimits::default(), |_| true, handler),
+            Some(json!([{"jsonrpc": "2.0", "id": null, "result": true}]))
+        );
+    }
+
+    #[test]
+    fn malformed_requests() {
+        assert_eq!(
+            handle_request_body("[", &RpcLimits::default(), |_| true, handler).unwrap()["error"]
+                ["code"],
+            json!(-32700)
+        );
+        assert_eq!(
+            handle_request_body("[]", &RpcLimits::default(), |_| true, handler).unwrap()["error"]
+                ["code"],
+            json!(-32600)
+        );
+    }
+
+    #[test]
+    fn engine_capabilities_are_served() {
+        let journal = EventJournal::default();
+        for (method, _) in engine::METHODS {
+            let req = RpcRequest {
+                id: Some(RpcRequestId::Number(1)),
                 jsonrpc: "2.0".to_string(),
-                error: error.into(),
-            })
-            .unwrap(),
-        ),
+                method: method.to_string(),
+                params: Some(vec![json!({})]),
+            };
+            assert!(
+                !matches!(map_requests(&req, &journal), Err(RpcErr::MethodNotFound(_))),
+                "{method} is not routed"
+            );
+        }
+
+        let req = RpcRequest {
+            id: Some(RpcRequestId::Number(1)),
+            jsonrpc: "2.0".to_string(),
+            method: "engine_exchangeCapabilities".to_string(),
+            params: Some(vec![json!(["engine_newPayloadV3", "engine_getPayloadV3"])]),
+        };
+        let served: Vec<&str> = engine::METHODS.iter().map(|(name, _)| *name).collect();
+        assert_eq!(map_requests(&req, &journal).unwrap(), json!(served));
     }
 }
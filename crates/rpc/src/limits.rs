@@ -0,0 +1,144 @@
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Limits applied to the requests served by the public HTTP endpoint.
+/// A `None` value disables the corresponding limit.
+#[derive(Debug, Clone, Default)]
+pub struct RpcLimits {
+    /// Maximum amount of requests accepted from a single IP address per second.
+    /// Every request inside a batch counts towards the limit.
+    pub requests_per_second: Option<u32>,
+    /// Maximum amount of requests inside a single batch.
+    pub max_batch_size: Option<usize>,
+    /// Maximum size in bytes of the result of a single request,
+    /// and of the whole response to a batch.
+    pub max_response_size: Option<usize>,
+    /// Methods that may be called. Entries are either full method names
+    /// or namespaces followed by a wildcard, such as `eth_*`.
+    pub allowed_methods: Option<Vec<String>>,
+    /// Methods that may not be called, using the same format as `allowed_methods`.
+    /// Takes precedence over the allowlist.
+    pub denied_methods: Vec<String>,
+    /// Requests that take longer than this to handle are logged as warnings.
+    pub slow_request_threshold: Option<Duration>,
+}
+
+impl RpcLimits {
+    pub fn is_method_allowed(&self, method: &str) -> bool {
+        let matches = |pattern: &String| match pattern.strip_suffix('*') {
+            Some(prefix) => method.starts_with(prefix),
+            None => method == pattern,
+        };
+        if self.denied_methods.iter().any(matches) {
+            return false;
+        }
+        self.allowed_methods
+            .as_ref()
+            .is_none_or(|allowed| allowed.iter().any(matches))
+    }
+}
+
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(1);
+
+/// Keeps track of the amount of requests issued by each IP address
+/// during the current one second window.
+#[derive(Debug)]
+pub struct RateLimiter {
+    state: Mutex<RateLimiterState>,
+}
+
+#[derive(Debug)]
+struct RateLimiterState {
+    windows: HashMap<IpAddr, (Instant, u32)>,
+    last_sweep: Instant,
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        RateLimiter {
+            state: Mutex::new(RateLimiterState {
+                windows: HashMap::new(),
+                last_sweep: Instant::now(),
+            }),
+        }
+    }
+}
+
+impl RateLimiter {
+    /// Registers `requests` new requests from `ip`, returning whether they are within `limit`.
+    pub fn check(&self, ip: IpAddr, requests: u32, limit: u32) -> bool {
+        self.check_at(ip, requests, limit, Instant::now())
+    }
+
+    fn check_at(&self, ip: IpAddr, requests: u32, limit: u32, now: Instant) -> bool {
+        let mut state = self.state.lock().unwrap();
+        // Drop the windows that already ended so the map doesn't grow unbounded. This is done
+        // at most once per window, so the cost of each request doesn't depend on the map size
+        if now.duration_since(state.last_sweep) >= RATE_LIMIT_WINDOW {
+            state
+                .windows
+                .retain(|_, (start, _)| now.duration_since(*start) < RATE_LIMIT_WINDOW);
+            state.last_sweep = now;
+        }
+        let (start, count) = state.windows.entry(ip).or_insert((now, 0));
+        if now.duration_since(*start) >= RATE_LIMIT_WINDOW {
+            *start = now;
+            *count = 0;
+        }
+        *count = count.saturating_add(requests);
+        *count <= limit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn method_allow_and_deny_lists() {
+        let limits = RpcLimits {
+            allowed_methods: Some(vec!["eth_*".to_string(), "net_version".to_string()]),
+            denied_methods: vec!["eth_sendRawTransaction".to_string()],
+            ..Default::default()
+        };
+        assert!(limits.is_method_allowed("eth_chainId"));
+        assert!(limits.is_method_allowed("net_version"));
+        assert!(!limits.is_method_allowed("net_peerCount"));
+        assert!(!limits.is_method_allowed("debug_getEventJournal"));
+        assert!(!limits.is_method_allowed("eth_sendRawTransaction"));
+        assert!(RpcLimits::default().is_method_allowed("debug_getEventJournal"));
+    }
+
+    #[test]
+    fn rate_limiter_counts_per_ip() {
+        let limiter = RateLimiter::default();
+        let ip: IpAddr = "127.0.0.1".parse().unwrap();
+        let other_ip: IpAddr = "127.0.0.2".parse().unwrap();
+        assert!(limiter.check(ip, 2, 3));
+        assert!(limiter.check(ip, 1, 3));
+        assert!(!limiter.check(ip, 1, 3));
+        assert!(limiter.check(other_ip, 3, 3));
+    }
+
+    #[test]
+    fn rate_limiter_windows_expire() {
+        let limiter = RateLimiter::default();
+        let ip: IpAddr = "127.0.0.1".parse().unwrap();
+        let other_ip: IpAddr = "127.0.0.2".parse().unwrap();
+        let start = Instant::now();
+        assert!(limiter.check_at(ip, 3, 3, start));
+        assert!(limiter.check_at(other_ip, 1, 3, start));
+        assert!(!limiter.check_at(ip, 1, 3, start + Duration::from_millis(500)));
+
+        // A new window starts for the IP, and the stale window of the other IP is dropped
+        let later = start + Duration::from_millis(1500);
+        assert!(limiter.check_at(ip, 3, 3, later));
+        let state = limiter.state.lock().unwrap();
+        assert_eq!(state.windows.len(), 1);
+        assert!(state.windows.contains_key(&ip));
+    }
+}
@@ -0,0 +1,26 @@
+use ethrex_core::U256;
+use serde_json::Value;
+
+use crate::utils::RpcErr;
+
+/// Returns the network id as a decimal string. The network id matches the chain id.
+pub fn version(chain_id: U256) -> Result<Value, RpcErr> {
+    Ok(Value::String(chain_id.to_string()))
+}
+
+// TODO: add `net_peerCount` and `net_listening` once the p2p layer accepts
+// connections and keeps a peer table
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_is_decimal_chain_id() {
+        let chain_id = U256::from(11155111);
+        assert_eq!(
+            version(chain_id).unwrap(),
+            Value::String("11155111".to_string())
+        );
+    }
+}
@@ -1,29 +1,76 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+/// Errors returned by the RPC handlers, each mapping to a JSON-RPC error code.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RpcErr {
-    MethodNotFound,
-    BadParams,
+    ParseError,
+    InvalidRequest,
+    MethodNotFound(String),
+    BadParams(String),
+    LimitExceeded(String),
+    Internal(String),
+    /// Execution reverted, with the hex-encoded revert data.
+    Revert {
+        data: String,
+    },
+    // Engine API errors
+    UnknownPayload,
+    InvalidForkchoiceState(String),
+    InvalidPayloadAttributes(String),
+    TooLargeRequest,
+    UnsupportedFork(String),
 }
 
 impl From<RpcErr> for RpcErrorMetadata {
     fn from(value: RpcErr) -> Self {
-        match value {
-            RpcErr::MethodNotFound => RpcErrorMetadata {
-                code: -32601,
-                message: "Method not found".to_string(),
-            },
-            RpcErr::BadParams => RpcErrorMetadata {
-                code: -1,
-                message: "Invalid params".to_string(),
-            },
+        let (code, message, data) = match value {
+            RpcErr::ParseError => (-32700, "Parse error".to_string(), None),
+            RpcErr::InvalidRequest => (-32600, "Invalid request".to_string(), None),
+            RpcErr::MethodNotFound(method) => (
+                -32601,
+                format!("The method {method} does not exist/is not available"),
+                None,
+            ),
+            RpcErr::BadParams(reason) => (-32602, format!("Invalid params: {reason}"), None),
+            RpcErr::LimitExceeded(reason) => (-32005, format!("Limit exceeded: {reason}"), None),
+            RpcErr::Internal(reason) => (-32603, format!("Internal Error: {reason}"), None),
+            RpcErr::Revert { data } => (3, "execution reverted".to_string(), Some(data)),
+            RpcErr::UnknownPayload => (-38001, "Unknown payload".to_string(), None),
+            RpcErr::InvalidForkchoiceState(reason) => {
+                (-38002, "Invalid forkchoice state".to_string(), Some(reason))
+            }
+            RpcErr::InvalidPayloadAttributes(reason) => (
+                -38003,
+                "Invalid payload attributes".to_string(),
+                Some(reason),
+            ),
+            RpcErr::TooLargeRequest => (-38004, "Too large request".to_string(), None),
+            RpcErr::UnsupportedFork(reason) => {
+                (-38005, "Unsupported fork".to_string(), Some(reason))
+            }
+        };
+        RpcErrorMetadata {
+            code,
+            message,
+            data,
         }
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum RpcRequestId {
+    Number(i64),
+    String(String),
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct RpcRequest {
-    pub id: i32,
+    /// Missing for notifications, which get no response, and null for requests
+    /// that don't need to tell their response apart.
+    #[serde(default)]
+    pub id: Option<RpcRequestId>,
     pub jsonrpc: String,
     pub method: String,
     pub params: Option<Vec<Value>>,
@@ -33,18 +80,52 @@ pub struct RpcRequest {
 pub struct RpcErrorMetadata {
     pub code: i32,
     pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct RpcSuccessResponse {
-    pub id: i32,
+    pub id: Option<RpcRequestId>,
     pub jsonrpc: String,
     pub result: Value,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct RpcErrorResponse {
-    pub id: i32,
+    /// Null when the id couldn't be read from the request.
+    pub id: Option<RpcRequestId>,
     pub jsonrpc: String,
     pub error: RpcErrorMetadata,
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn error_codes() {
+        let cases = [
+            (RpcErr::BadParams("expected 1 params".to_string()), -32602),
+            (RpcErr::Internal("poisoned lock".to_string()), -32603),
+            (RpcErr::UnknownPayload, -38001),
+            (RpcErr::UnsupportedFork("Prague".to_string()), -38005),
+        ];
+        for (err, code) in cases {
+            assert_eq!(RpcErrorMetadata::from(err).code, code);
+        }
+    }
+
+    #[test]
+    fn revert_carries_data() {
+        let metadata = RpcErrorMetadata::from(RpcErr::Revert {
+            data: "0x08c379a0".to_string(),
+        });
+        assert_eq!(
+            serde_json::to_value(metadata).unwrap(),
+            json!({"code": 3, "message": "execution reverted", "data": "0x08c379a0"})
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use ethrex_core::hash::keccak;
+use serde_json::Value;
+
+use crate::utils::RpcErr;
+
+/// Client version reported to peers and tooling, in the usual
+/// `name/version-commit/target` format.
+pub const CLIENT_VERSION: &str = concat!(
+    "ethrex/v",
+    env!("CARGO_PKG_VERSION"),
+    "-",
+    env!("ETHREX_GIT_COMMIT"),
+    "/",
+    env!("ETHREX_TARGET")
+);
+
+pub fn client_version() -> Result<Value, RpcErr> {
+    Ok(Value::String(CLIENT_VERSION.to_string()))
+}
+
+/// Returns the Keccak-256 hash of the given hex-encoded data.
+pub fn sha3(data: &Value) -> Result<Value, RpcErr> {
+    let data = data
+        .as_str()
+        .and_then(|data| data.strip_prefix("0x"))
+        .ok_or(RpcErr::BadParams(
+            "expected 0x-prefixed hex data".to_string(),
+        ))?;
+    let data = hex::decode(data).map_err(|err| RpcErr::BadParams(err.to_string()))?;
+    Ok(Value::String(format!("{:#x}", keccak(data))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn client_version_format() {
+        let version = client_version().unwrap();
+        let version = version.as_str().unwrap();
+        assert!(version.starts_with("ethrex/v0.1.0-"));
+        assert_eq!(version.split('/').count(), 3);
+    }
+
+    #[test]
+    fn sha3_hashes_hex_data() {
+        assert_eq!(
+            sha3(&Value::from("0x68656c6c6f20776f726c64")).unwrap(),
+            Value::from("0x47173285a8d7341e5e972fc677286384f802f8ef42a5ec5f03bbfa254cb01fad")
+        );
+        assert_eq!(
+            sha3(&Value::from("0x")).unwrap(),
+            Value::from("0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470")
+        );
+        assert!(sha3(&Value::from("68656c6c6f")).is_err());
+        assert!(sha3(&Value::from("0x6")).is_err());
+    }
+}
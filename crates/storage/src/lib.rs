@@ -10,7 +10,7 @@ use block::{BlockBodyRLP, BlockHeaderRLP};
 use ethrex_core::types::{BlockNumber, Index};
 use libmdbx::{
     dupsort,
-    orm::{table, Database},
+    orm::{table, Database, DatabaseChart},
     table_info,
 };
 use receipt::ReceiptRLP;
@@ -45,7 +45,18 @@ dupsort!(
 /// Initializes a new database with the provided path. If the path is `None`, the database
 /// will be temporary.
 pub fn init_db(path: Option<impl AsRef<Path>>) -> Database {
-    let tables = [
+    let path = path.map(|p| p.as_ref().to_path_buf());
+    Database::create(path, &tables()).unwrap()
+}
+
+/// Opens an existing database at the provided path without write permissions, for inspecting
+/// it while the node is stopped or serving reads alongside it. Write transactions fail.
+pub fn open_db_read_only(path: impl AsRef<Path>) -> anyhow::Result<Database> {
+    Database::open(path, &tables())
+}
+
+fn tables() -> DatabaseChart {
+    [
         table_info!(Headers),
         table_info!(Bodies),
         table_info!(AccountInfos),
@@ -54,9 +65,7 @@ pub fn init_db(path: Option<impl AsRef<Path>>) -> Database {
         table_info!(Receipts),
     ]
     .into_iter()
-    .collect();
-    let path = path.map(|p| p.as_ref().to_path_buf());
-    Database::create(path, &tables).unwrap()
+    .collect()
 }
 
 #[cfg(test)]
@@ -66,6 +75,19 @@ mod tests {
         table_info,
     };
 
+    #[test]
+    fn read_only_db() {
+        let dir = tempfile::tempdir().unwrap();
+        drop(super::init_db(Some(dir.path())));
+
+        let db = super::open_db_read_only(dir.path()).unwrap();
+        {
+            let txn = db.begin_read().unwrap();
+            assert!(txn.get::<super::Headers>(0).unwrap().is_none());
+        }
+        assert!(db.begin_readwrite().is_err());
+    }
+
     #[test]
     fn mdbx_smoke_test() {
         // Declare tables used for the smoke test
@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use ef_tests::{
+    evm::{execute_transaction, ExecutionMode},
+    types::TestUnit,
+};
+
+fn read_test_unit() -> TestUnit {
+    let s = std::fs::read_to_string("./vectors/add11.json").expect("Unable to read file");
+    let tests: HashMap<String, TestUnit> = serde_json::from_str(&s).expect("Unable to parse JSON");
+    tests
+        .into_values()
+        .next()
+        .expect("Vector should contain a test")
+}
+
+fn execution_benchmark(c: &mut Criterion) {
+    let test = read_test_unit();
+    let transaction = test
+        .blocks
+        .first()
+        .unwrap()
+        .transactions
+        .as_ref()
+        .unwrap()
+        .first()
+        .unwrap();
+
+    let mut group = c.benchmark_group("execute add11 transaction");
+    for (name, mode) in [
+        ("default", ExecutionMode::Default),
+        ("eip-3155 trace", ExecutionMode::Trace),
+    ] {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                execute_transaction(
+                    &test.genesis_block_header,
+                    transaction,
+                    test.pre.clone(),
+                    test.network,
+                    mode,
+                )
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, execution_benchmark);
+criterion_main!(benches);
@@ -1,32 +1,72 @@
-use std::{collections::HashMap, io::stderr};
+use std::{collections::HashMap, convert::Infallible, io::stderr};
 
 use ethrex_core::{Address, U256};
 use revm::{
     inspector_handle_register,
     inspectors::TracerEip3155,
     primitives::{
-        keccak256, AccountInfo, Bytecode, Env, ExecutionResult, FixedBytes, SpecId, TransactTo,
-        U256 as AlloyU256,
+        keccak256, AccountInfo, Bytecode, EVMError, Env, ExecutionResult, FixedBytes, SpecId,
+        TransactTo, U256 as AlloyU256,
     },
     Evm,
 };
 
-use crate::types::{Account, Header, Transaction};
+use crate::types::{Account, Header, Network, Transaction};
+
+/// How a transaction should be executed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExecutionMode {
+    /// Plain execution, without any inspector attached.
+    #[default]
+    Default,
+    /// Execution writing an EIP-3155 trace of every step to stderr.
+    Trace,
+}
+
+impl From<Network> for SpecId {
+    fn from(network: Network) -> Self {
+        match network {
+            Network::Frontier => SpecId::FRONTIER,
+            Network::Homestead => SpecId::HOMESTEAD,
+            Network::Tangerine => SpecId::TANGERINE,
+            Network::SpuriousDragon => SpecId::SPURIOUS_DRAGON,
+            Network::Byzantium => SpecId::BYZANTIUM,
+            Network::Constantinople => SpecId::CONSTANTINOPLE,
+            Network::Petersburg => SpecId::PETERSBURG,
+            Network::Istanbul => SpecId::ISTANBUL,
+            Network::Berlin => SpecId::BERLIN,
+            Network::London => SpecId::LONDON,
+            Network::Merge => SpecId::MERGE,
+            Network::Shanghai => SpecId::SHANGHAI,
+            Network::Cancun => SpecId::CANCUN,
+        }
+    }
+}
 
 pub fn execute_transaction(
     block: &Header,
     transaction: &Transaction,
     pre: HashMap<Address, Account>,
-) -> ExecutionResult {
+    network: Network,
+    mode: ExecutionMode,
+) -> Result<ExecutionResult, EVMError<Infallible>> {
     let mut env = Box::<Env>::default();
 
     env.block.number = to_alloy_bytes(block.number);
     env.block.coinbase = block.coinbase.to_fixed_bytes().into();
     env.block.timestamp = to_alloy_bytes(block.timestamp);
     env.block.gas_limit = to_alloy_bytes(block.gas_limit);
-    env.block.basefee = AlloyU256::ZERO;
-    env.block.difficulty = AlloyU256::ZERO;
-    env.block.prevrandao = Some(block.mix_hash.as_fixed_bytes().into());
+    env.block.basefee = block
+        .base_fee_per_gas
+        .map(to_alloy_bytes)
+        .unwrap_or_default();
+    env.block.difficulty = to_alloy_bytes(block.difficulty);
+
+    let spec_id = SpecId::from(network);
+    // The mix hash field holds the previous RANDAO value since the merge
+    if spec_id >= SpecId::MERGE {
+        env.block.prevrandao = Some(block.mix_hash.as_fixed_bytes().into());
+    }
 
     env.tx.caller = transaction.sender.to_fixed_bytes().into();
 
@@ -38,8 +78,6 @@ pub fn execute_transaction(
     );
     env.tx.gas_priority_fee = transaction.max_priority_fee_per_gas.map(to_alloy_bytes);
 
-    let spec_id = SpecId::CANCUN;
-
     env.tx.gas_limit = transaction.gas_limit.as_u64();
 
     env.tx.data = transaction.data.clone();
@@ -69,21 +107,19 @@ pub fn execute_transaction(
         .with_cached_prestate(cache)
         .with_bundle_update()
         .build();
-    let evm = Evm::builder()
+    let evm_builder = Evm::builder()
         .with_db(&mut state)
         .modify_env(|e| e.clone_from(&env))
-        .with_spec_id(spec_id)
-        .build();
-
-    let mut evm = evm
-        .modify()
-        .reset_handler_with_external_context(
-            TracerEip3155::new(Box::new(stderr())).without_summary(),
-        )
-        .append_handler_register(inspector_handle_register)
-        .build();
-
-    evm.transact_commit().unwrap()
+        .with_spec_id(spec_id);
+
+    match mode {
+        ExecutionMode::Default => evm_builder.build().transact_commit(),
+        ExecutionMode::Trace => evm_builder
+            .with_external_context(TracerEip3155::new(Box::new(stderr())).without_summary())
+            .append_handler_register(inspector_handle_register)
+            .build()
+            .transact_commit(),
+    }
 }
 
 fn to_alloy_bytes(eth_byte: U256) -> AlloyU256 {
@@ -0,0 +1,69 @@
+use revm::primitives::{EVMError, InvalidTransaction};
+
+/// Returns the identifier used by the EF tests' `expectException` field for the
+/// given execution error, if there is an equivalent one.
+pub fn exception_name<DBError>(error: &EVMError<DBError>) -> Option<&'static str> {
+    let EVMError::Transaction(error) = error else {
+        return None;
+    };
+    let name = match error {
+        InvalidTransaction::PriorityFeeGreaterThanMaxFee => {
+            "TransactionException.PRIORITY_GREATER_THAN_MAX_FEE_PER_GAS"
+        }
+        InvalidTransaction::GasPriceLessThanBasefee => {
+            "TransactionException.INSUFFICIENT_MAX_FEE_PER_GAS"
+        }
+        InvalidTransaction::CallerGasLimitMoreThanBlock => {
+            "TransactionException.GAS_ALLOWANCE_EXCEEDED"
+        }
+        InvalidTransaction::CallGasCostMoreThanGasLimit => {
+            "TransactionException.INTRINSIC_GAS_TOO_LOW"
+        }
+        InvalidTransaction::RejectCallerWithCode => "TransactionException.SENDER_NOT_EOA",
+        InvalidTransaction::LackOfFundForMaxFee { .. } => {
+            "TransactionException.INSUFFICIENT_ACCOUNT_FUNDS"
+        }
+        InvalidTransaction::NonceOverflowInTransaction => "TransactionException.NONCE_IS_MAX",
+        InvalidTransaction::NonceTooHigh { .. } => "TransactionException.NONCE_MISMATCH_TOO_HIGH",
+        InvalidTransaction::NonceTooLow { .. } => "TransactionException.NONCE_MISMATCH_TOO_LOW",
+        InvalidTransaction::CreateInitCodeSizeLimit => {
+            "TransactionException.INITCODE_SIZE_EXCEEDED"
+        }
+        InvalidTransaction::AccessListNotSupported => "TransactionException.TYPE_1_TX_PRE_FORK",
+        InvalidTransaction::MaxFeePerBlobGasNotSupported
+        | InvalidTransaction::BlobVersionedHashesNotSupported => {
+            "TransactionException.TYPE_3_TX_PRE_FORK"
+        }
+        InvalidTransaction::BlobGasPriceGreaterThanMax => {
+            "TransactionException.INSUFFICIENT_MAX_FEE_PER_BLOB_GAS"
+        }
+        InvalidTransaction::EmptyBlobs => "TransactionException.TYPE_3_TX_ZERO_BLOBS",
+        InvalidTransaction::BlobCreateTransaction => {
+            "TransactionException.TYPE_3_TX_CONTRACT_CREATION"
+        }
+        InvalidTransaction::TooManyBlobs { .. } => {
+            "TransactionException.TYPE_3_TX_MAX_BLOB_GAS_ALLOWANCE_EXCEEDED"
+        }
+        InvalidTransaction::BlobVersionNotSupported => {
+            "TransactionException.TYPE_3_TX_INVALID_BLOB_VERSIONED_HASH"
+        }
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Checks whether the error matches the fixture's `expectException` value,
+/// which may list several accepted exceptions separated by `|`.
+pub fn matches_expected_exception<DBError>(expected: &str, error: &EVMError<DBError>) -> bool {
+    exception_name(error)
+        .is_some_and(|name| expected.split('|').any(|expected| expected.trim() == name))
+}
+
+/// Checks whether the fixture's `expectException` value only lists block
+/// exceptions. These come from validating the block, like its header fields or
+/// gas used, and not from executing its transactions.
+pub fn is_block_exception(expected: &str) -> bool {
+    expected
+        .split('|')
+        .all(|expected| expected.trim().starts_with("BlockException."))
+}
@@ -1,2 +1,3 @@
 pub mod evm;
+pub mod exception;
 pub mod types;
@@ -14,12 +14,34 @@ pub struct TestUnit {
     #[serde(rename = "genesisRLP")]
     pub genesis_rlp: serde_json::Value,
     pub lastblockhash: serde_json::Value,
-    pub network: serde_json::Value,
+    pub network: Network,
     pub post_state: serde_json::Value,
     pub pre: HashMap<Address, Account>,
     pub seal_engine: serde_json::Value,
 }
 
+/// Fork whose rules apply to every block of the test.
+#[derive(Debug, PartialEq, Eq, Deserialize, Clone, Copy)]
+pub enum Network {
+    Frontier,
+    Homestead,
+    #[serde(rename = "EIP150")]
+    Tangerine,
+    #[serde(rename = "EIP158")]
+    SpuriousDragon,
+    Byzantium,
+    Constantinople,
+    #[serde(alias = "ConstantinopleFix")]
+    Petersburg,
+    Istanbul,
+    Berlin,
+    London,
+    #[serde(alias = "Paris")]
+    Merge,
+    Shanghai,
+    Cancun,
+}
+
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
 pub struct Account {
     pub balance: U256,
@@ -89,6 +111,7 @@ pub struct Block {
     pub rlp: Bytes,
     pub transactions: Option<Vec<Transaction>>,
     pub uncle_headers: Option<Vec<Header>>,
+    pub expect_exception: Option<String>,
 }
 
 #[derive(Debug, PartialEq, Eq, Deserialize, Clone)]
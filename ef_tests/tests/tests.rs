@@ -1,17 +1,47 @@
-use ::ef_tests::{evm::execute_transaction, types::TestUnit};
+use ::ef_tests::{
+    evm::{execute_transaction, ExecutionMode},
+    exception::{is_block_exception, matches_expected_exception},
+    types::TestUnit,
+};
 
 fn execute_test(test: TestUnit) {
     // TODO: Add support for multiple blocks and multiple transactions per block.
-    let transaction = test
-        .blocks
-        .first()
-        .unwrap()
-        .transactions
+    let block = test.blocks.first().unwrap();
+    if let Some(expected) = block
+        .expect_exception
         .as_ref()
-        .unwrap()
-        .first()
-        .unwrap();
-    execute_transaction(&test.genesis_block_header, transaction, test.pre);
+        .filter(|expected| is_block_exception(expected))
+    {
+        // TODO: validate blocks before executing their transactions
+        eprintln!("Skipping block expecting {expected}: the runner doesn't validate blocks");
+        return;
+    }
+    let transaction = block.transactions.as_ref().unwrap().first().unwrap();
+    // The transaction runs in the context of its own block, not its parent's.
+    // Invalid blocks don't come with a decoded header, so those fall back to
+    // the genesis header.
+    let header = block
+        .block_header
+        .as_ref()
+        .unwrap_or(&test.genesis_block_header);
+    let result = execute_transaction(
+        header,
+        transaction,
+        test.pre,
+        test.network,
+        ExecutionMode::Default,
+    );
+    match (&block.expect_exception, result) {
+        (None, Ok(_)) => {}
+        (None, Err(error)) => panic!("Unexpected execution error: {error:?}"),
+        (Some(expected), Ok(_)) => {
+            panic!("Expected exception {expected}, but the execution succeeded")
+        }
+        (Some(expected), Err(error)) => assert!(
+            matches_expected_exception(expected, &error),
+            "Expected exception {expected}, got {error:?}"
+        ),
+    }
 }
 
 #[cfg(test)]
@@ -4,9 +4,18 @@ pub fn cli() -> Command {
     Command::new("Ethrex")
         .about("Ethereum Rust Execution client")
         .author("Lambdaclass")
+        .arg(
+            Arg::new("config")
+                .long("config")
+                .env("ETHREX_CONFIG")
+                .value_name("CONFIG_FILE_PATH")
+                .help("TOML file with default values for any of the other options, which are overridden by command line flags and environment variables")
+                .action(ArgAction::Set),
+        )
         .arg(
             Arg::new("http.addr")
                 .long("http.addr")
+                .env("ETHREX_HTTP_ADDR")
                 .default_value("localhost")
                 .value_name("ADDRESS")
                 .action(ArgAction::Set),
@@ -14,13 +23,72 @@ pub fn cli() -> Command {
         .arg(
             Arg::new("http.port")
                 .long("http.port")
+                .env("ETHREX_HTTP_PORT")
                 .default_value("8545")
                 .value_name("PORT")
                 .action(ArgAction::Set),
         )
+        .arg(
+            Arg::new("http.corsdomain")
+                .long("http.corsdomain")
+                .env("ETHREX_HTTP_CORSDOMAIN")
+                .value_name("ORIGIN_LIST")
+                .help("Origins allowed to make cross-origin HTTP requests, or * to allow any")
+                .value_delimiter(',')
+                .num_args(1..)
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("http.ratelimit")
+                .long("http.ratelimit")
+                .value_name("REQUESTS_PER_SECOND")
+                .help("Maximum amount of HTTP requests accepted per second from a single IP")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("http.maxbatchsize")
+                .long("http.maxbatchsize")
+                .value_name("REQUESTS")
+                .help("Maximum amount of requests in a single HTTP batch")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("http.maxresponsesize")
+                .long("http.maxresponsesize")
+                .value_name("BYTES")
+                .help("Maximum size in bytes of the result of a single HTTP request, and of a whole batch response")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("http.api.allow")
+                .long("http.api.allow")
+                .value_name("METHOD_LIST")
+                .help("Methods allowed over HTTP, such as eth_chainId or eth_*")
+                .value_delimiter(',')
+                .num_args(1..)
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("http.api.deny")
+                .long("http.api.deny")
+                .value_name("METHOD_LIST")
+                .help("Methods denied over HTTP, such as debug_*")
+                .value_delimiter(',')
+                .num_args(1..)
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("rpc.slowrequestthreshold")
+                .long("rpc.slowrequestthreshold")
+                .default_value("1000")
+                .value_name("MILLISECONDS")
+                .help("Log a warning for RPC requests that take longer than this to handle")
+                .action(ArgAction::Set),
+        )
         .arg(
             Arg::new("authrpc.addr")
                 .long("authrpc.addr")
+                .env("ETHREX_AUTHRPC_ADDR")
                 .default_value("localhost")
                 .value_name("ADDRESS")
                 .action(ArgAction::Set),
@@ -28,6 +96,7 @@ pub fn cli() -> Command {
         .arg(
             Arg::new("authrpc.port")
                 .long("authrpc.port")
+                .env("ETHREX_AUTHRPC_PORT")
                 .default_value("8551")
                 .value_name("PORT")
                 .action(ArgAction::Set),
@@ -60,6 +129,15 @@ pub fn cli() -> Command {
                 .value_name("PORT")
                 .action(ArgAction::Set),
         )
+        .arg(
+            Arg::new("discovery.dns")
+                .long("discovery.dns")
+                .value_name("ENRTREE_LIST")
+                .help("Comma separated enrtree:// links of DNS trees to fetch nodes from (EIP-1459)")
+                .value_delimiter(',')
+                .num_args(1..)
+                .action(ArgAction::Set),
+        )
         .arg(
             Arg::new("network")
                 .long("network")
@@ -67,6 +145,20 @@ pub fn cli() -> Command {
                 .value_name("GENESIS_FILE_PATH")
                 .action(ArgAction::Set),
         )
+        .arg(
+            Arg::new("datadir")
+                .long("datadir")
+                .value_name("DATABASE_DIRECTORY")
+                .help("Directory where the node's database and key are stored [default: $XDG_DATA_HOME/ethrex or ~/.ethrex]")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("nodekey")
+                .long("nodekey")
+                .value_name("NODE_KEY_FILE")
+                .help("File with the node's hex-encoded secret key, created if missing [default: <DATABASE_DIRECTORY>/nodekey]")
+                .action(ArgAction::Set),
+        )
         .arg(
             Arg::new("bootnodes")
                 .long("bootnodes")
@@ -0,0 +1,225 @@
+use std::path::Path;
+
+use clap::Command;
+use serde::Deserialize;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("Failed to read config file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to parse config file: {0}")]
+    Parse(#[from] toml::de::Error),
+}
+
+/// Options that can be set in the TOML config file. Tables match the prefix of
+/// the CLI arguments, so `port` inside the `[http]` table sets `http.port`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub http: HttpConfig,
+    pub rpc: RpcConfig,
+    pub authrpc: AuthRpcConfig,
+    pub p2p: P2PConfig,
+    pub discovery: DiscoveryConfig,
+    pub network: Option<String>,
+    pub datadir: Option<String>,
+    pub nodekey: Option<String>,
+    pub bootnodes: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HttpConfig {
+    pub addr: Option<String>,
+    pub port: Option<u16>,
+    pub corsdomain: Option<Vec<String>>,
+    pub ratelimit: Option<u32>,
+    pub maxbatchsize: Option<usize>,
+    pub maxresponsesize: Option<usize>,
+    pub api: HttpApiConfig,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HttpApiConfig {
+    pub allow: Option<Vec<String>>,
+    pub deny: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RpcConfig {
+    pub slowrequestthreshold: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuthRpcConfig {
+    pub addr: Option<String>,
+    pub port: Option<u16>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct P2PConfig {
+    pub addr: Option<String>,
+    pub port: Option<u16>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DiscoveryConfig {
+    pub addr: Option<String>,
+    pub port: Option<u16>,
+    pub dns: Option<Vec<String>>,
+}
+
+impl Config {
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let contents = std::fs::read_to_string(path)?;
+        Ok(toml::from_str(&contents)?)
+    }
+
+    /// Uses the config file options as the defaults of the command's arguments,
+    /// so they are overridden by command line flags and environment variables.
+    pub fn apply_defaults(&self, command: Command) -> Command {
+        let command = set_default(command, "http.addr", &self.http.addr);
+        let command = set_default(command, "http.port", &self.http.port);
+        let command = set_defaults(command, "http.corsdomain", &self.http.corsdomain);
+        let command = set_default(command, "http.ratelimit", &self.http.ratelimit);
+        let command = set_default(command, "http.maxbatchsize", &self.http.maxbatchsize);
+        let command = set_default(command, "http.maxresponsesize", &self.http.maxresponsesize);
+        let command = set_defaults(command, "http.api.allow", &self.http.api.allow);
+        let command = set_defaults(command, "http.api.deny", &self.http.api.deny);
+        let command = set_default(
+            command,
+            "rpc.slowrequestthreshold",
+            &self.rpc.slowrequestthreshold,
+        );
+        let command = set_default(command, "authrpc.addr", &self.authrpc.addr);
+        let command = set_default(command, "authrpc.port", &self.authrpc.port);
+        let command = set_default(command, "p2p.addr", &self.p2p.addr);
+        let command = set_default(command, "p2p.port", &self.p2p.port);
+        let command = set_default(command, "discovery.addr", &self.discovery.addr);
+        let command = set_default(command, "discovery.port", &self.discovery.port);
+        let command = set_defaults(command, "discovery.dns", &self.discovery.dns);
+        let command = set_default(command, "network", &self.network);
+        let command = set_default(command, "datadir", &self.datadir);
+        let command = set_default(command, "nodekey", &self.nodekey);
+        set_defaults(command, "bootnodes", &self.bootnodes)
+    }
+}
+
+fn set_default(command: Command, id: &str, value: &Option<impl ToString>) -> Command {
+    match value {
+        Some(value) => command.mut_arg(id, |arg| arg.default_value(value.to_string())),
+        None => command,
+    }
+}
+
+fn set_defaults(command: Command, id: &str, values: &Option<Vec<String>>) -> Command {
+    match values {
+        Some(values) => command.mut_arg(id, |arg| arg.default_values(values.clone())),
+        None => command,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli;
+    use std::io::Write;
+
+    fn write_config(contents: &str) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        file
+    }
+
+    #[test]
+    fn config_file_is_overridden_by_command_line() {
+        let file = write_config(
+            r#"
+            datadir = "-data"
+            bootnodes = ["enode://a", "enode://b"]
+
+            [http]
+            addr = "0.0.0.0"
+            port = 9545
+            corsdomain = ["*"]
+            "#,
+        );
+        let config = Config::read(file.path()).unwrap();
+        let matches =
+            config
+                .apply_defaults(cli::cli())
+                .get_matches_from(["ethrex", "--http.port", "8000"]);
+
+        let get = |id| matches.get_one::<String>(id).unwrap().as_str();
+        assert_eq!(get("datadir"), "-data");
+        assert_eq!(get("http.addr"), "0.0.0.0");
+        assert_eq!(get("http.port"), "8000");
+        assert_eq!(get("authrpc.port"), "8551");
+        let bootnodes: Vec<_> = matches.get_many::<String>("bootnodes").unwrap().collect();
+        assert_eq!(bootnodes, ["enode://a", "enode://b"]);
+        let origins: Vec<_> = matches
+            .get_many::<String>("http.corsdomain")
+            .unwrap()
+            .collect();
+        assert_eq!(origins, ["*"]);
+    }
+
+    #[test]
+    fn every_option_maps_to_an_argument() {
+        let file = write_config(
+            r#"
+            network = "genesis.json"
+            datadir = "data"
+            nodekey = "nodekey"
+            bootnodes = ["enode://a"]
+
+            [http]
+            addr = "localhost"
+            port = 8545
+            corsdomain = ["*"]
+            ratelimit = 10
+            maxbatchsize = 100
+            maxresponsesize = 1000
+            api = { allow = ["eth_*"], deny = ["debug_*"] }
+
+            [rpc]
+            slowrequestthreshold = 500
+
+            [authrpc]
+            addr = "localhost"
+            port = 8551
+
+            [p2p]
+            addr = "0.0.0.0"
+            port = 30303
+
+            [discovery]
+            addr = "0.0.0.0"
+            port = 30303
+            dns = ["enrtree://a@nodes.example.org"]
+            "#,
+        );
+        let config = Config::read(file.path()).unwrap();
+        // Panics if any of the ids doesn't match an argument
+        config.apply_defaults(cli::cli()).debug_assert();
+    }
+
+    #[test]
+    fn invalid_options_are_rejected() {
+        let file = write_config("[http]\nprot = 8545\n");
+        let error = Config::read(file.path()).unwrap_err();
+        assert!(matches!(&error, ConfigError::Parse(_)));
+        assert!(error.to_string().contains("unknown field `prot`"));
+
+        let file = write_config("[http]\nport = \"8545\"\n");
+        assert!(matches!(
+            Config::read(file.path()),
+            Err(ConfigError::Parse(_))
+        ));
+    }
+}
@@ -1,14 +1,29 @@
+use clap::ArgMatches;
 use ethrex_core::types::Genesis;
-use ethrex_net::types::BootNode;
+use ethrex_net::{
+    dns_discovery::TreeLink,
+    node_id_from_signing_key,
+    types::{BootNode, Node, NodeRecord},
+};
+use ethrex_rpc::limits::RpcLimits;
 use std::{
+    ffi::OsString,
     io::{self, BufReader},
     net::{SocketAddr, ToSocketAddrs},
+    path::PathBuf,
     str::FromStr,
+    time::Duration,
 };
 use tokio::try_join;
-use tracing::Level;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn, Level};
 use tracing_subscriber::FmtSubscriber;
 mod cli;
+mod config;
+mod node_key;
+
+/// Time given to the node's services to stop after a shutdown signal is received.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
 
 #[tokio::main]
 async fn main() {
@@ -18,7 +33,12 @@ async fn main() {
 
     tracing::subscriber::set_global_default(subscriber).expect("setting default subscriber failed");
 
-    let matches = cli::cli().get_matches();
+    let matches = parse_args();
+
+    let data_dir = matches
+        .get_one::<String>("datadir")
+        .map(PathBuf::from)
+        .unwrap_or_else(default_data_dir);
 
     let http_addr = matches
         .get_one::<String>("http.addr")
@@ -33,6 +53,35 @@ async fn main() {
         .get_one::<String>("authrpc.port")
         .expect("authrpc.port is required");
 
+    let cors_allowed_origins: Vec<String> = matches
+        .get_many::<String>("http.corsdomain")
+        .map(|origins| origins.cloned().collect())
+        .unwrap_or_default();
+
+    let rpc_limits = RpcLimits {
+        requests_per_second: matches
+            .get_one::<String>("http.ratelimit")
+            .map(|s| s.parse().expect("Failed to parse http.ratelimit")),
+        max_batch_size: matches
+            .get_one::<String>("http.maxbatchsize")
+            .map(|s| s.parse().expect("Failed to parse http.maxbatchsize")),
+        max_response_size: matches
+            .get_one::<String>("http.maxresponsesize")
+            .map(|s| s.parse().expect("Failed to parse http.maxresponsesize")),
+        allowed_methods: matches
+            .get_many::<String>("http.api.allow")
+            .map(|methods| methods.cloned().collect()),
+        denied_methods: matches
+            .get_many::<String>("http.api.deny")
+            .map(|methods| methods.cloned().collect())
+            .unwrap_or_default(),
+        slow_request_threshold: matches
+            .get_one::<String>("rpc.slowrequestthreshold")
+            .map(|s| {
+                Duration::from_millis(s.parse().expect("Failed to parse rpc.slowrequestthreshold"))
+            }),
+    };
+
     let tcp_addr = matches
         .get_one::<String>("p2p.addr")
         .expect("addr is required");
@@ -53,12 +102,19 @@ async fn main() {
     let bootnode_list: Vec<_> = matches
         .get_many::<String>("bootnodes")
         .expect("bootnodes is required")
+        .filter(|s| !s.is_empty())
         .collect();
 
-    let _bootnodes: Vec<BootNode> = bootnode_list
-        .iter()
-        .map(|s| BootNode::from_str(s).expect("Failed to parse bootnodes"))
-        .collect();
+    let _bootnodes: Vec<BootNode> = bootnode_list.iter().map(|s| parse_bootnode(s)).collect();
+
+    let dns_trees: Vec<TreeLink> = matches
+        .get_many::<String>("discovery.dns")
+        .map(|links| {
+            links
+                .map(|link| TreeLink::from_str(link).expect("Failed to parse DNS tree link"))
+                .collect()
+        })
+        .unwrap_or_default();
 
     let http_socket_addr =
         parse_socket_addr(http_addr, http_port).expect("Failed to parse http address and port");
@@ -70,12 +126,129 @@ async fn main() {
     let tcp_socket_addr =
         parse_socket_addr(tcp_addr, tcp_port).expect("Failed to parse addr and port");
 
-    let _genesis = read_genesis_file(genesis_file_path);
+    let genesis = read_genesis_file(genesis_file_path);
+
+    let node_key_path = matches
+        .get_one::<String>("nodekey")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| data_dir.join("nodekey"));
+    let signer = node_key::load_or_create_node_key(&node_key_path).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(1)
+    });
+    let local_node = Node {
+        ip: tcp_socket_addr.ip(),
+        udp_port: udp_socket_addr.port(),
+        tcp_port: tcp_socket_addr.port(),
+        node_id: node_id_from_signing_key(&signer),
+    };
+    let local_node_record =
+        node_key::load_or_create_node_record(data_dir.join("noderecord"), &local_node, &signer)
+            .unwrap_or_else(|e| {
+                eprintln!("{e}");
+                std::process::exit(1)
+            });
+
+    let cancel_token = CancellationToken::new();
+
+    let rpc_api = ethrex_rpc::start_api(
+        http_socket_addr,
+        authrpc_socket_addr,
+        local_node,
+        local_node_record.clone(),
+        genesis.config.chain_id,
+        rpc_limits,
+        cors_allowed_origins,
+        cancel_token.clone(),
+    );
+    let networking = ethrex_net::start_network(
+        udp_socket_addr,
+        tcp_socket_addr,
+        signer,
+        local_node_record,
+        dns_trees,
+        cancel_token.clone(),
+    );
 
-    let rpc_api = ethrex_rpc::start_api(http_socket_addr, authrpc_socket_addr);
-    let networking = ethrex_net::start_network(udp_socket_addr, tcp_socket_addr);
+    let mut rpc_api = tokio::spawn(rpc_api);
+    let mut networking = tokio::spawn(networking);
+
+    tokio::select! {
+        res = async { try_join!(&mut rpc_api, &mut networking) } => {
+            res.unwrap();
+        }
+        _ = shutdown_signal() => {
+            info!("Shutting down");
+            cancel_token.cancel();
+            let services = async { try_join!(rpc_api, networking) };
+            match tokio::time::timeout(SHUTDOWN_TIMEOUT, services).await {
+                Ok(res) => {
+                    res.unwrap();
+                    info!("Shutdown complete");
+                }
+                Err(_) => warn!("Services didn't stop in time, exiting anyway"),
+            }
+        }
+    }
+}
+
+/// Completes when the process receives SIGINT (Ctrl+C) or SIGTERM.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
+
+/// Parses the command line arguments, using the config file options
+/// for the arguments that weren't set otherwise.
+fn parse_args() -> ArgMatches {
+    let args: Vec<OsString> = std::env::args_os().collect();
+    let matches = cli::cli().get_matches_from(&args);
+    let Some(config_path) = matches.get_one::<String>("config") else {
+        return matches;
+    };
+    let config = config::Config::read(config_path).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(1)
+    });
+    config.apply_defaults(cli::cli()).get_matches_from(args)
+}
+
+/// Per-user data directory, following the XDG base directory spec when it's configured.
+fn default_data_dir() -> PathBuf {
+    if let Some(data_home) = std::env::var_os("XDG_DATA_HOME").filter(|dir| !dir.is_empty()) {
+        return PathBuf::from(data_home).join("ethrex");
+    }
+    let home = std::env::var_os("HOME").unwrap_or_default();
+    PathBuf::from(home).join(".ethrex")
+}
 
-    try_join!(tokio::spawn(rpc_api), tokio::spawn(networking)).unwrap();
+/// Parses a bootnode given either as an enode url or as a node record.
+fn parse_bootnode(s: &str) -> BootNode {
+    if s.starts_with("enr:") {
+        let record = NodeRecord::from_str(s).expect("Failed to parse bootnode record");
+        let node = record
+            .to_node()
+            .expect("Bootnode record doesn't have an address and ports");
+        return node.into();
+    }
+    BootNode::from_str(s).expect("Failed to parse bootnodes")
 }
 
 fn read_genesis_file(genesis_file_path: &str) -> Genesis {
@@ -85,12 +258,13 @@ fn read_genesis_file(genesis_file_path: &str) -> Genesis {
 }
 
 fn parse_socket_addr(addr: &str, port: &str) -> io::Result<SocketAddr> {
-    // NOTE: this blocks until hostname can be resolved
-    format!("{addr}:{port}")
-        .to_socket_addrs()?
-        .next()
-        .ok_or(io::Error::new(
-            io::ErrorKind::NotFound,
-            "Failed to parse socket address",
-        ))
+    let port: u16 = port
+        .parse()
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "Failed to parse port"))?;
+    // NOTE: IP literals (including IPv6 ones such as `::`) are parsed directly,
+    // anything else is treated as a hostname and blocks until it can be resolved
+    (addr, port).to_socket_addrs()?.next().ok_or(io::Error::new(
+        io::ErrorKind::NotFound,
+        "Failed to parse socket address",
+    ))
 }
@@ -0,0 +1,136 @@
+use std::{
+    fs::OpenOptions,
+    io::{ErrorKind, Write},
+    path::Path,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use ethrex_net::types::{Node, NodeRecord};
+use k256::{ecdsa::SigningKey, elliptic_curve::rand_core::OsRng};
+
+#[derive(Debug, thiserror::Error)]
+pub enum NodeKeyError {
+    #[error("Failed to access node identity file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid node key, expected 32 hex-encoded bytes")]
+    InvalidKey,
+}
+
+/// Loads the node's secret key from a file holding it hex-encoded, so the node keeps its
+/// identity across restarts. If the file doesn't exist, a new key is generated and stored.
+pub fn load_or_create_node_key(path: impl AsRef<Path>) -> Result<SigningKey, NodeKeyError> {
+    let path = path.as_ref();
+    if path.exists() {
+        let contents = std::fs::read_to_string(path)?;
+        let key = hex::decode(contents.trim()).map_err(|_| NodeKeyError::InvalidKey)?;
+        return SigningKey::from_slice(&key).map_err(|_| NodeKeyError::InvalidKey);
+    }
+
+    let key = SigningKey::random(&mut OsRng);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    // The key identifies the node in the network, so only its owner may read it
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(path)?;
+    file.write_all(hex::encode(key.to_bytes()).as_bytes())?;
+    Ok(key)
+}
+
+/// Returns the node record for the node's current endpoints. The record is stored at the given
+/// path, so its sequence number is only increased when the endpoints change. Records created
+/// from scratch are numbered with the current unix time in milliseconds, so the sequence
+/// number doesn't go back even if the stored record is lost.
+pub fn load_or_create_node_record(
+    path: impl AsRef<Path>,
+    node: &Node,
+    signer: &SigningKey,
+) -> Result<NodeRecord, NodeKeyError> {
+    let path = path.as_ref();
+    let stored = match std::fs::read_to_string(path) {
+        // Records of a previous node key are replaced
+        Ok(contents) => NodeRecord::from_str(contents.trim())
+            .ok()
+            .filter(|record| record.public_key().as_ref() == Some(signer.verifying_key())),
+        Err(e) if e.kind() == ErrorKind::NotFound => None,
+        Err(e) => return Err(e.into()),
+    };
+    let record = match &stored {
+        Some(stored) => stored.update(node, signer),
+        None => {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default();
+            NodeRecord::new(node, now.as_millis() as u64, signer)
+        }
+    };
+    if stored.as_ref() != Some(&record) {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, record.to_string())?;
+    }
+    Ok(record)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn node_key_is_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+        // The key's directory is created along with it
+        let path = dir.path().join("datadir").join("nodekey");
+
+        let key = load_or_create_node_key(&path).unwrap();
+        assert_eq!(load_or_create_node_key(&path).unwrap(), key);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        std::fs::write(&path, "not a key").unwrap();
+        assert!(matches!(
+            load_or_create_node_key(&path),
+            Err(NodeKeyError::InvalidKey)
+        ));
+    }
+
+    #[test]
+    fn node_record_sequence_survives_restarts() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("noderecord");
+        let signer = SigningKey::random(&mut OsRng);
+        let node = Node {
+            ip: "127.0.0.1".parse().unwrap(),
+            udp_port: 30303,
+            tcp_port: 30303,
+            node_id: ethrex_net::node_id_from_signing_key(&signer),
+        };
+
+        let record = load_or_create_node_record(&path, &node, &signer).unwrap();
+        assert!(record.seq > 1);
+        assert_eq!(
+            load_or_create_node_record(&path, &node, &signer).unwrap(),
+            record
+        );
+
+        let moved = Node {
+            tcp_port: 30304,
+            ..node
+        };
+        let updated = load_or_create_node_record(&path, &moved, &signer).unwrap();
+        assert_eq!(updated.seq, record.seq + 1);
+        assert_eq!(
+            load_or_create_node_record(&path, &moved, &signer).unwrap(),
+            updated
+        );
+    }
+}